@@ -2,5 +2,8 @@ mod app;
 mod pages;
 
 fn main() {
-    next_rs::Renderer::<app::App>::new().render();
+    next_rs::Document::new()
+        .lang("en")
+        .body_class("bg-gray-900")
+        .mount::<app::App>(());
 }
@@ -29,7 +29,7 @@ pub fn LandingPage() -> Html {
                     <Link to="#portfolio" aria_current="page" class="text-lg hover:underline">{"Portfolio"}</Link>
                     <Link to="#services" class="text-lg hover:underline">{"Services"}</Link>
                     <Link to="#not-found" scroll_offset=300.0 scroll=true scroll_behavior="smooth" class="text-lg hover:underline">{"Not Found"}</Link>
-                    <Link to="/" target="_self" prefetch=true class="text-lg hover:underline">{"Link with Prefetch"}</Link>
+                    <Link to="/" target="_self" prefetch=true exact=true active_class="font-bold" class="text-lg hover:underline">{"Link with Prefetch"}</Link>
                 </nav>
                 <section id="home" class="mb-12">
                     <h2 class="text-3xl font-semibold mb-4">{"Unleash Creativity and Innovation"}</h2>
@@ -0,0 +1,248 @@
+use crate::head::{use_default_metadata, Head};
+use crate::metadata::{Alternate, Metadata};
+use crate::prelude::*;
+use crate::router::{use_route, use_router};
+use web_sys::window;
+
+/// Returns `path` without its locale prefix, such as `/pricing` for `/fr/pricing`.
+///
+/// # Example
+/// ```
+/// use next_rs::alternates::strip_locale;
+///
+/// let locales = ["en", "fr"];
+/// assert_eq!(strip_locale("/fr/pricing", &locales), "/pricing");
+/// assert_eq!(strip_locale("/fr", &locales), "/");
+/// assert_eq!(strip_locale("/pricing", &locales), "/pricing");
+/// ```
+pub fn strip_locale<'a, L: AsRef<str>>(path: &'a str, locales: &[L]) -> &'a str {
+    let trimmed = path.trim_start_matches('/');
+    let segment = trimmed.split('/').next().unwrap_or("");
+    if !locales.iter().any(|locale| locale.as_ref() == segment) {
+        return path;
+    }
+    match &trimmed[segment.len()..] {
+        "" => "/",
+        rest => rest,
+    }
+}
+
+/// Returns the path of `path` in `locale`, the default locale being served without a prefix.
+///
+/// # Example
+/// ```
+/// use next_rs::alternates::localize_path;
+///
+/// assert_eq!(localize_path("/pricing", "fr", "en"), "/fr/pricing");
+/// assert_eq!(localize_path("/", "fr", "en"), "/fr");
+/// assert_eq!(localize_path("/pricing", "en", "en"), "/pricing");
+/// ```
+pub fn localize_path(path: &str, locale: &str, default_locale: &str) -> String {
+    match (locale == default_locale, path) {
+        (true, _) => path.to_string(),
+        (false, "/") => format!("/{}", locale),
+        (false, _) => format!("/{}{}", locale, path),
+    }
+}
+
+/// Builds the alternate links of a page in every locale, plus an `x-default` one pointing at the
+/// default locale.
+///
+/// # Arguments
+///
+/// * `path` - The path of the page in any locale, without the basename.
+/// * `locales` - The locales of the site.
+/// * `default_locale` - The locale served without a prefix.
+/// * `href` - Turns a localized path into the URL of the link, such as by adding the basename.
+///
+/// # Returns
+///
+/// (Vec<Alternate>): One alternate per locale, then the `x-default` one.
+pub fn locale_alternates<L, F>(
+    path: &str,
+    locales: &[L],
+    default_locale: &str,
+    href: F,
+) -> Vec<Alternate>
+where
+    L: AsRef<str>,
+    F: Fn(&str) -> String,
+{
+    let path = strip_locale(path, locales);
+    let alternate = |hreflang: &str, locale: &str| Alternate {
+        href: href(&localize_path(path, locale, default_locale)).into(),
+        hreflang: Some(hreflang.to_string().into()),
+        ..Alternate::default()
+    };
+
+    let mut alternates: Vec<Alternate> = locales
+        .iter()
+        .map(|locale| alternate(locale.as_ref(), locale.as_ref()))
+        .collect();
+    if !alternates.is_empty() {
+        alternates.push(alternate("x-default", default_locale));
+    }
+    alternates
+}
+
+/// Props for [`Alternates`].
+#[derive(Properties, Clone, PartialEq)]
+pub struct AlternatesProps {
+    /// The origin of the site, such as `https://acme.dev`. Defaults to the `origin` of the site
+    /// metadata, then to the origin of the current page.
+    #[prop_or_default]
+    pub origin: AttrValue,
+
+    /// The locales of the site. With the `i18n` feature, defaults to the supported languages of
+    /// the `I18nProvider`.
+    #[prop_or_default]
+    pub locales: Vec<AttrValue>,
+
+    /// The locale served without a prefix, and targeted by `x-default`. Defaults to the first
+    /// locale.
+    #[prop_or_default]
+    pub default_locale: AttrValue,
+}
+
+/// Renders the canonical URL of the current page and its alternates in every locale.
+///
+/// Localized pages are expected under a locale prefix, like `/fr/pricing`, except for the default
+/// locale. Every URL includes the basename of the router, so the component must be rendered inside
+/// a router. URLs are absolute when an origin is known; on the server, that takes the `origin`
+/// prop or the `origin` of the site metadata, and without either the URLs are relative.
+///
+/// # Example
+/// ```rust,no_run
+/// use next_rs::prelude::*;
+/// use next_rs::alternates::Alternates;
+///
+/// #[func]
+/// pub fn Layout() -> Html {
+///     let locales = vec!["en".into(), "fr".into(), "de".into()];
+///
+///     rsx! {
+///         <Alternates origin="https://acme.dev" locales={locales} default_locale="en" />
+///     }
+/// }
+/// ```
+#[func]
+pub fn Alternates(props: &AlternatesProps) -> Html {
+    let router = use_router();
+    let route = use_route();
+    let defaults = use_default_metadata();
+    #[cfg(feature = "i18n")]
+    let supported_languages: Vec<AttrValue> = crate::use_translation()
+        .config
+        .supported_languages
+        .iter()
+        .map(|locale| AttrValue::from(*locale))
+        .collect();
+    #[cfg(not(feature = "i18n"))]
+    let supported_languages: Vec<AttrValue> = Vec::new();
+
+    let locales = match props.locales.is_empty() {
+        true => supported_languages,
+        false => props.locales.clone(),
+    };
+
+    let origin: String = [Some(props.origin.clone()), defaults.origin.clone()]
+        .into_iter()
+        .flatten()
+        .find(|origin| !origin.is_empty())
+        .map(|origin| origin.to_string())
+        .or_else(|| match cfg!(target_arch = "wasm32") {
+            true => window().and_then(|win| win.location().origin().ok()),
+            false => None,
+        })
+        .unwrap_or_default();
+    let origin = origin.trim_end_matches('/');
+
+    let default_locale = match props.default_locale.is_empty() {
+        true => locales.first().cloned().unwrap_or_default(),
+        false => props.default_locale.clone(),
+    };
+    let href = |path: &str| format!("{}{}", origin, router.prefix_basename(path));
+
+    let path = route.split(['?', '#']).next().unwrap_or("/");
+    let meta = Metadata {
+        canonical: Some(href(path).into()),
+        alternates: locale_alternates(path, &locales, &default_locale, href),
+        ..Metadata::default()
+    };
+
+    rsx! {
+        <Head>{meta.to_html()}</Head>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hrefs(alternates: &[Alternate]) -> Vec<(String, String)> {
+        alternates
+            .iter()
+            .map(|alternate| {
+                (
+                    alternate
+                        .hreflang
+                        .as_deref()
+                        .unwrap_or_default()
+                        .to_string(),
+                    alternate.href.to_string(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn strip_locale_only_strips_known_locale_segments() {
+        let locales = ["en", "fr"];
+        assert_eq!(strip_locale("/fr/pricing", &locales), "/pricing");
+        assert_eq!(strip_locale("/fr", &locales), "/");
+        assert_eq!(strip_locale("/french/pricing", &locales), "/french/pricing");
+        assert_eq!(strip_locale("/pricing", &locales), "/pricing");
+        assert_eq!(strip_locale("/fr/pricing", &[] as &[&str]), "/fr/pricing");
+    }
+
+    #[test]
+    fn localize_path_prefixes_other_locales() {
+        assert_eq!(localize_path("/pricing", "fr", "en"), "/fr/pricing");
+        assert_eq!(localize_path("/", "fr", "en"), "/fr");
+        assert_eq!(localize_path("/pricing", "en", "en"), "/pricing");
+        assert_eq!(localize_path("/", "en", "en"), "/");
+    }
+
+    #[test]
+    fn locale_alternates_include_the_basename_and_x_default() {
+        let alternates = locale_alternates("/fr/pricing", &["en", "fr"], "en", |path| {
+            format!("https://acme.dev/docs{}", path)
+        });
+        assert_eq!(
+            hrefs(&alternates),
+            [
+                ("en".into(), "https://acme.dev/docs/pricing".into()),
+                ("fr".into(), "https://acme.dev/docs/fr/pricing".into()),
+                ("x-default".into(), "https://acme.dev/docs/pricing".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn locale_alternates_without_locales_are_empty() {
+        let alternates = locale_alternates("/pricing", &[] as &[&str], "", str::to_string);
+        assert!(alternates.is_empty());
+    }
+
+    #[test]
+    fn locale_alternates_with_the_default_locale_only() {
+        let alternates = locale_alternates("/pricing", &["en"], "en", str::to_string);
+        assert_eq!(
+            hrefs(&alternates),
+            [
+                ("en".into(), "/pricing".into()),
+                ("x-default".into(), "/pricing".into()),
+            ]
+        );
+    }
+}
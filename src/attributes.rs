@@ -0,0 +1,391 @@
+use crate::head::{HeadContext, Owner, OwnerStack};
+use crate::prelude::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use web_sys::{window, Element};
+
+thread_local! {
+    // The attributes set on `<html>` and `<body>` by mounted components, keyed by element.
+    static OWNERS: RefCell<HashMap<&'static str, AttributeOwners>> = RefCell::new(HashMap::new());
+}
+
+/// The attributes set on an element by each mounted component, and the values the element had
+/// before.
+#[derive(Debug, Default)]
+pub(crate) struct AttributeOwners {
+    entries: OwnerStack<Vec<(String, String)>>,
+    original: HashMap<String, Option<String>>,
+}
+
+impl AttributeOwners {
+    /// Resolves the value of every managed attribute.
+    ///
+    /// Classes of every owner are added to the original ones, while other attributes are taken
+    /// from the deepest owner setting them, then the last mounted one. Attributes no owner sets anymore get their
+    /// original value back, or are removed if they had none.
+    pub(crate) fn resolve(&self) -> Vec<(String, Option<String>)> {
+        let mut resolved: Vec<(String, Option<String>)> = Vec::new();
+        let mut set = |name: &str, value: Option<String>| match resolved
+            .iter_mut()
+            .find(|(key, _)| key == name)
+        {
+            Some(entry) => entry.1 = value,
+            None => resolved.push((name.to_string(), value)),
+        };
+
+        for (name, value) in &self.original {
+            set(name, value.clone());
+        }
+        for attributes in self.entries.values() {
+            for (name, value) in attributes {
+                set(name, Some(value.clone()));
+            }
+        }
+
+        let classes: Vec<&str> = self
+            .original
+            .get("class")
+            .and_then(|class| class.as_deref())
+            .into_iter()
+            .chain(self.entries.values().flat_map(|attributes| {
+                attributes
+                    .iter()
+                    .filter(|(name, _)| name == "class")
+                    .map(|(_, value)| value.as_str())
+            }))
+            .flat_map(str::split_whitespace)
+            .fold(Vec::new(), |mut classes, class| {
+                if !classes.contains(&class) {
+                    classes.push(class);
+                }
+                classes
+            });
+        if !classes.is_empty() {
+            set("class", Some(classes.join(" ")));
+        }
+
+        resolved
+    }
+
+    /// Sets the attributes of `owner`.
+    pub(crate) fn register(&mut self, owner: Owner, attributes: Vec<(String, String)>) {
+        self.entries.register(owner, attributes);
+    }
+}
+
+/// Returns the `<html>` or `<body>` element, or `None` outside of a browser, such as on the
+/// server.
+fn target_element(target: &'static str) -> Option<Element> {
+    if !cfg!(target_arch = "wasm32") {
+        return None;
+    }
+    let document = window().and_then(|win| win.document())?;
+    match target {
+        "html" => document.document_element(),
+        _ => document.body().map(Into::into),
+    }
+}
+
+/// Applies the attributes of every owner of `target` to the element.
+fn apply(target: &'static str) {
+    let element = match target_element(target) {
+        Some(element) => element,
+        None => return,
+    };
+
+    OWNERS.with(|owners| {
+        let mut owners = owners.borrow_mut();
+        let owners = owners.entry(target).or_default();
+
+        for attributes in owners.entries.values() {
+            for (name, _) in attributes {
+                if !owners.original.contains_key(name) {
+                    owners
+                        .original
+                        .insert(name.clone(), element.get_attribute(name));
+                }
+            }
+        }
+
+        for (name, value) in owners.resolve() {
+            let _ = match value {
+                Some(value) => element.set_attribute(&name, &value),
+                None => element.remove_attribute(&name),
+            };
+        }
+
+        // Attributes given back to the page are no longer tracked.
+        let entries = &owners.entries;
+        owners.original.retain(|name, _| {
+            entries
+                .values()
+                .any(|attributes| attributes.iter().any(|(key, _)| key == name))
+        });
+    });
+}
+
+/// Sets the attributes of `owner` on `target`.
+fn register(target: &'static str, owner: Owner, attributes: Vec<(String, String)>) {
+    OWNERS.with(|owners| {
+        owners
+            .borrow_mut()
+            .entry(target)
+            .or_default()
+            .register(owner, attributes)
+    });
+    apply(target);
+}
+
+/// Removes the attributes of the owner `id` from `target`.
+fn unregister(target: &'static str, id: usize) {
+    OWNERS.with(|owners| {
+        if let Some(owners) = owners.borrow_mut().get_mut(target) {
+            owners.entries.unregister(id);
+        }
+    });
+    apply(target);
+}
+
+/// Props for [`HtmlAttributes`] and [`BodyAttributes`].
+#[derive(Properties, Clone, PartialEq)]
+pub struct AttributesProps {
+    /// The language of the document, such as `en`.
+    #[prop_or_default]
+    pub lang: AttrValue,
+
+    /// The text direction, `ltr`, `rtl` or `auto`.
+    #[prop_or_default]
+    pub dir: AttrValue,
+
+    /// Classes added to the element, such as `dark` or `modal-open`.
+    #[prop_or_default]
+    pub class: AttrValue,
+
+    /// Other attributes, such as `("data-theme", "dark")`.
+    #[prop_or_default]
+    pub attributes: Vec<(&'static str, AttrValue)>,
+}
+
+impl AttributesProps {
+    /// Returns the attributes that are set, by name.
+    fn to_attributes(&self) -> Vec<(String, String)> {
+        [
+            ("lang", &self.lang),
+            ("dir", &self.dir),
+            ("class", &self.class),
+        ]
+        .into_iter()
+        .chain(self.attributes.iter().map(|(name, value)| (*name, value)))
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+    }
+}
+
+/// Props for [`AttributesRegistration`].
+#[derive(Properties, Clone, PartialEq)]
+struct AttributesRegistrationProps {
+    target: &'static str,
+    attributes: Vec<(String, String)>,
+}
+
+/// Sets attributes on `<html>` or `<body>` while mounted, owned at its depth in the component
+/// tree like the registration of a `Head`.
+struct AttributesRegistration {
+    owner: Owner,
+}
+
+impl Component for AttributesRegistration {
+    type Message = ();
+    type Properties = AttributesRegistrationProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let owner = Owner::of(ctx.link());
+        let collector = ctx
+            .link()
+            .context::<HeadContext>(Callback::noop())
+            .and_then(|(context, _)| context.collector);
+
+        // Effects don't run on the server, so register while rendering.
+        if let Some(collector) = collector {
+            let props = ctx.props();
+            collector.register_attributes(props.target, owner, props.attributes.clone());
+        }
+
+        Self { owner }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props() != old_props {
+            register(
+                ctx.props().target,
+                self.owner,
+                ctx.props().attributes.clone(),
+            );
+        }
+        false
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        Html::default()
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if first_render {
+            register(
+                ctx.props().target,
+                self.owner,
+                ctx.props().attributes.clone(),
+            );
+        }
+    }
+
+    fn destroy(&mut self, ctx: &Context<Self>) {
+        unregister(ctx.props().target, self.owner.id);
+    }
+}
+
+/// Sets attributes on the `<html>` element while mounted.
+///
+/// Like `Head`, the most deeply nested component wins for each attribute, then the one mounted
+/// last, except for classes which are combined. The previous values are restored when it unmounts. On the server, the attributes
+/// are collected by the [`crate::HeadCollector`] and rendered by `Document::render`.
+///
+/// # Arguments
+/// * `props` - The properties of the component.
+///
+/// # Returns
+/// (Html): Nothing, the attributes are set on the element directly.
+///
+/// # Examples
+/// ```
+/// use next_rs::prelude::*;
+/// use next_rs::HtmlAttributes;
+///
+/// #[func]
+/// pub fn ArabicPage() -> Html {
+///     rsx! {
+///         <HtmlAttributes lang="ar" dir="rtl" />
+///     }
+/// }
+/// ```
+#[func]
+pub fn HtmlAttributes(props: &AttributesProps) -> Html {
+    rsx! { <AttributesRegistration target="html" attributes={props.to_attributes()} /> }
+}
+
+/// Sets attributes on the `<body>` element while mounted.
+///
+/// Like `Head`, the most deeply nested component wins for each attribute, then the one mounted
+/// last, except for classes which are combined. The previous values are restored when it unmounts. On the server, the attributes
+/// are collected by the [`crate::HeadCollector`] and rendered by `Document::render`.
+///
+/// # Arguments
+/// * `props` - The properties of the component.
+///
+/// # Returns
+/// (Html): Nothing, the attributes are set on the element directly.
+///
+/// # Examples
+/// ```
+/// use next_rs::prelude::*;
+/// use next_rs::BodyAttributes;
+///
+/// #[func]
+/// pub fn Modal() -> Html {
+///     rsx! {
+///         <>
+///             <BodyAttributes class="modal-open" />
+///             <div class="modal" />
+///         </>
+///     }
+/// }
+/// ```
+#[func]
+pub fn BodyAttributes(props: &AttributesProps) -> Html {
+    rsx! { <AttributesRegistration target="body" attributes={props.to_attributes()} /> }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attributes(attributes: &[(&str, &str)]) -> Vec<(String, String)> {
+        attributes
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn page_attributes_are_collected_on_the_server() {
+        use crate::head::{HeadCollector, HeadProvider, HeadProviderProps};
+
+        #[func]
+        fn Page() -> Html {
+            rsx! { <HtmlAttributes lang="fr" class="dark" /> }
+        }
+
+        #[func]
+        fn Layout() -> Html {
+            rsx! {
+                <>
+                    <HtmlAttributes lang="en" class="app" />
+                    <Page />
+                </>
+            }
+        }
+
+        let collector = HeadCollector::new();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        tokio::task::LocalSet::new().block_on(
+            &runtime,
+            yew::LocalServerRenderer::<HeadProvider>::with_props(HeadProviderProps {
+                children: rsx! { <Layout /> },
+                collector: Some(collector.clone()),
+            })
+            .render(),
+        );
+
+        let mut html = collector.attributes("html");
+        html.sort();
+        assert_eq!(html, attributes(&[("class", "app dark"), ("lang", "fr")]));
+    }
+
+    #[test]
+    fn deepest_owner_wins_and_classes_are_combined() {
+        let mut owners = AttributeOwners::default();
+        let page = Owner::new(3);
+        // A layout mounted after the page, such as after it was re-rendered.
+        let layout = Owner::new(1);
+        owners
+            .original
+            .insert("class".to_string(), Some("base".to_string()));
+        owners.register(page, attributes(&[("lang", "fr"), ("class", "dark")]));
+        owners.register(layout, attributes(&[("lang", "en"), ("class", "base app")]));
+
+        let mut resolved = owners.resolve();
+        resolved.sort();
+        assert_eq!(
+            resolved,
+            [
+                ("class".to_string(), Some("base app dark".to_string())),
+                ("lang".to_string(), Some("fr".to_string())),
+            ]
+        );
+
+        owners.entries.unregister(page.id);
+        let mut resolved = owners.resolve();
+        resolved.sort();
+        assert_eq!(
+            resolved,
+            [
+                ("class".to_string(), Some("base app".to_string())),
+                ("lang".to_string(), Some("en".to_string())),
+            ]
+        );
+    }
+}
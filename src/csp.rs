@@ -0,0 +1,286 @@
+use crate::prelude::*;
+use crate::use_context;
+use std::fmt;
+use web_sys::js_sys::Reflect;
+use web_sys::window;
+
+/// The name of the Content Security Policy header.
+pub const HEADER: &str = "Content-Security-Policy";
+
+/// Directives ignored by browsers when the policy is delivered in a `<meta>` tag.
+const HEADER_ONLY_DIRECTIVES: [&str; 4] = ["frame-ancestors", "report-uri", "report-to", "sandbox"];
+
+/// Directives the nonce is added to.
+const NONCE_DIRECTIVES: [&str; 4] = [
+    "script-src",
+    "script-src-elem",
+    "style-src",
+    "style-src-elem",
+];
+
+/// A Content Security Policy, producing the value of the `Content-Security-Policy` header or of
+/// its `<meta http-equiv>` equivalent.
+///
+/// The nonce must be freshly generated for every response by the server, which also passes it to
+/// [`CspProvider`] so that `Script`, `Head` and styles carry it.
+///
+/// # Example
+/// ```
+/// use next_rs::csp::CspConfig;
+///
+/// let csp = CspConfig::strict("r4nd0m").source("img-src", "https://images.acme.dev");
+///
+/// assert_eq!(
+///     csp.to_string(),
+///     "default-src 'self'; \
+///      script-src 'self' 'strict-dynamic' 'wasm-unsafe-eval' 'nonce-r4nd0m'; \
+///      style-src 'self' 'nonce-r4nd0m'; img-src 'self' data: https://images.acme.dev; \
+///      font-src 'self'; object-src 'none'; base-uri 'self'; frame-ancestors 'none'"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CspConfig {
+    /// The directives of the policy and their sources, in order.
+    pub directives: Vec<(String, Vec<String>)>,
+    /// The nonce allowing inline scripts and styles, without the `nonce-` prefix.
+    pub nonce: Option<String>,
+}
+
+impl CspConfig {
+    /// Creates an empty policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a strict policy for the nonce of the response, only allowing resources of the same
+    /// origin, the wasm of the app, and nonced scripts and styles.
+    ///
+    /// `'wasm-unsafe-eval'` lets the browser compile the wasm module without allowing `eval`, and
+    /// `'strict-dynamic'` trusts the scripts loaded by nonced ones, so the glue script loading the
+    /// app must carry the nonce, as it does when rendered by [`crate::Document`]. Inline `style`
+    /// attributes are blocked, so images should use `styling="class"`. Styles stylist injects in
+    /// the browser carry no nonce either: render them on the server with [`nonce_styles`], or
+    /// relax `style-src`.
+    pub fn strict(nonce: impl Into<String>) -> Self {
+        Self::new()
+            .nonce(nonce)
+            .directive("default-src", &["'self'"])
+            .directive(
+                "script-src",
+                &["'self'", "'strict-dynamic'", "'wasm-unsafe-eval'"],
+            )
+            .directive("style-src", &["'self'"])
+            .directive("img-src", &["'self'", "data:"])
+            .directive("font-src", &["'self'"])
+            .directive("object-src", &["'none'"])
+            .directive("base-uri", &["'self'"])
+            .directive("frame-ancestors", &["'none'"])
+    }
+
+    /// Sets the sources of a directive, replacing the previous ones.
+    pub fn directive(mut self, name: &str, sources: &[&str]) -> Self {
+        let sources: Vec<String> = sources.iter().map(ToString::to_string).collect();
+        match self.directives.iter_mut().find(|(key, _)| key == name) {
+            Some(directive) => directive.1 = sources,
+            None => self.directives.push((name.to_string(), sources)),
+        }
+        self
+    }
+
+    /// Adds a source to a directive.
+    pub fn source(mut self, name: &str, source: &str) -> Self {
+        match self.directives.iter_mut().find(|(key, _)| key == name) {
+            Some((_, sources)) if !sources.iter().any(|s| s == source) => {
+                sources.push(source.to_string())
+            }
+            Some(_) => {}
+            None => self
+                .directives
+                .push((name.to_string(), vec![source.to_string()])),
+        }
+        self
+    }
+
+    /// Sets the nonce of the response.
+    pub fn nonce(mut self, nonce: impl Into<String>) -> Self {
+        self.nonce = Some(nonce.into());
+        self
+    }
+
+    /// Renders the directives, keeping those accepted by `keep`.
+    fn render(&self, keep: impl Fn(&str) -> bool) -> String {
+        self.directives
+            .iter()
+            .filter(|(name, _)| keep(name))
+            .map(|(name, sources)| {
+                let nonce = self
+                    .nonce
+                    .as_ref()
+                    .filter(|_| NONCE_DIRECTIVES.contains(&name.as_str()))
+                    .map(|nonce| format!("'nonce-{}'", nonce));
+                let sources: Vec<String> = sources.iter().cloned().chain(nonce).collect();
+                match sources.is_empty() {
+                    true => name.clone(),
+                    false => format!("{} {}", name, sources.join(" ")),
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("; ")
+    }
+
+    /// Returns the value of the `Content-Security-Policy` header.
+    pub fn to_header_value(&self) -> String {
+        self.render(|_| true)
+    }
+
+    /// Returns the value of the `<meta http-equiv>` tag, without the directives browsers only
+    /// accept in the header, such as `frame-ancestors`.
+    pub fn to_meta_value(&self) -> String {
+        self.render(|name| !HEADER_ONLY_DIRECTIVES.contains(&name))
+    }
+
+    /// Renders the policy as a `<meta http-equiv="Content-Security-Policy">` tag, to be placed
+    /// inside `Head`, for hosts where response headers can't be set.
+    pub fn to_html(&self) -> Html {
+        rsx! { <meta http-equiv={HEADER} content={self.to_meta_value()} /> }
+    }
+}
+
+impl fmt::Display for CspConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_header_value())
+    }
+}
+
+/// Represents the nonce of the current response.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CspContext {
+    /// The nonce allowing inline scripts and styles.
+    pub nonce: AttrValue,
+}
+
+/// Props for [`CspProvider`].
+#[derive(Properties, Clone, PartialEq)]
+pub struct CspProviderProps {
+    /// The nonce of the response. In the browser, defaults to the nonce of the first nonced
+    /// script or style of the document, such as the one loading the app.
+    #[prop_or_default]
+    pub nonce: AttrValue,
+    /// Children components to be rendered.
+    #[prop_or_default]
+    pub children: Html,
+}
+
+/// Returns the nonce of the first nonced script or style of the document.
+///
+/// Browsers hide the `nonce` attribute once the document is parsed, so it is read from the
+/// `nonce` property instead.
+fn document_nonce() -> Option<AttrValue> {
+    if !cfg!(target_arch = "wasm32") {
+        return None;
+    }
+    let element = window()
+        .and_then(|win| win.document())
+        .and_then(|doc| doc.query_selector("script[nonce], style[nonce]").ok())
+        .flatten()?;
+    Reflect::get(&element, &"nonce".into())
+        .ok()
+        .and_then(|nonce| nonce.as_string())
+        .filter(|nonce| !nonce.is_empty())
+        .map(Into::into)
+}
+
+/// Provides the nonce of the response to `Script`, `Head` and other components adding inline
+/// scripts or styles.
+///
+/// # Examples
+/// ```
+/// use next_rs::prelude::*;
+/// use next_rs::csp::CspProvider;
+/// use next_rs::{Head, Script};
+///
+/// #[func]
+/// pub fn App() -> Html {
+///     rsx! {
+///         <CspProvider nonce="r4nd0m">
+///             <Head>
+///                 <style>{"body { margin: 0; }"}</style>
+///             </Head>
+///             <Script id="init" content="window.ready = true;" />
+///         </CspProvider>
+///     }
+/// }
+/// ```
+#[func]
+pub fn CspProvider(props: &CspProviderProps) -> Html {
+    let nonce = {
+        let nonce = props.nonce.clone();
+        use_memo(nonce, |nonce| match nonce.is_empty() {
+            true => document_nonce().unwrap_or_default(),
+            false => nonce.clone(),
+        })
+    };
+    let context = CspContext {
+        nonce: (*nonce).clone(),
+    };
+
+    rsx! {
+        <ContextProvider<CspContext> context={context}>
+            {props.children.clone()}
+        </ContextProvider<CspContext>>
+    }
+}
+
+/// A hook to access the nonce of the response.
+///
+/// It returns `None` outside of a [`CspProvider`], or when no nonce is known.
+#[hook]
+pub fn use_nonce() -> Option<AttrValue> {
+    use_context::<CspContext>()
+        .map(|context| context.nonce)
+        .filter(|nonce| !nonce.is_empty())
+}
+
+/// Adds `nonce` to every `<style>` tag of server-rendered markup lacking one, such as the styles
+/// collected by stylist's `StaticReader`.
+///
+/// Styles stylist injects in the browser are created from code without a nonce, so a policy with
+/// a nonce blocks them. Render the styles on the server, or allow them separately by leaving the
+/// nonce out of `style-src`.
+///
+/// # Example
+/// ```
+/// use next_rs::csp::nonce_styles;
+///
+/// let markup = nonce_styles(r#"<style data-style="stylist-a1">.a { color: red; }</style>"#, "r4nd0m");
+/// assert_eq!(
+///     markup,
+///     r#"<style nonce="r4nd0m" data-style="stylist-a1">.a { color: red; }</style>"#
+/// );
+///
+/// let markup = nonce_styles(r#"<style nonce="abc">.a { color: red; }</style>"#, "r4nd0m");
+/// assert_eq!(markup, r#"<style nonce="abc">.a { color: red; }</style>"#);
+/// ```
+#[cfg(feature = "css")]
+pub fn nonce_styles(markup: &str, nonce: &str) -> String {
+    let nonce = nonce.replace('&', "&amp;").replace('"', "&quot;");
+    let mut result = String::with_capacity(markup.len());
+    let mut rest = markup;
+
+    while let Some(start) = rest.find("<style") {
+        let (before, after) = rest.split_at(start + "<style".len());
+        result.push_str(before);
+        rest = after;
+
+        let end = after.find('>').unwrap_or(after.len());
+        let is_style = after.starts_with(|c: char| c == '>' || c == '/' || c.is_whitespace());
+        let has_nonce = after[..end]
+            .split_whitespace()
+            .any(|attribute| attribute == "nonce" || attribute.starts_with("nonce="));
+        if is_style && !has_nonce {
+            result.push_str(&format!(" nonce=\"{}\"", nonce));
+        }
+    }
+    result.push_str(rest);
+    result
+}
@@ -0,0 +1,389 @@
+use crate::attributes::{BodyAttributes, HtmlAttributes};
+use crate::csp::CspProvider;
+use crate::head::{HeadCollector, HeadProvider, MetadataProvider};
+use crate::log;
+use crate::metadata::Metadata;
+use crate::prelude::*;
+use std::rc::Rc;
+use web_sys::{window, Element};
+use yew::html::BaseComponent;
+use yew::virtual_dom::VChild;
+
+/// Where the scripts of a [`Document`] are placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScriptPlacement {
+    /// In `<head>`. Module scripts are deferred, so they still run once the body is parsed.
+    Head,
+    /// At the end of `<body>`, after the root element.
+    #[default]
+    BodyEnd,
+}
+
+/// The HTML shell of the app, the equivalent of an `index.html` defined in Rust.
+///
+/// The same document is used to mount the app in the browser with [`Document::mount`] and to
+/// render complete pages on the server or at build time with [`Document::to_html`].
+///
+/// # Example
+/// ```rust,no_run
+/// use next_rs::prelude::*;
+/// use next_rs::document::Document;
+/// use next_rs::metadata::Metadata;
+///
+/// #[func]
+/// fn App() -> Html {
+///     rsx! { <h1>{"Next RS"}</h1> }
+/// }
+///
+/// fn main() {
+///     Document::new()
+///         .lang("en")
+///         .body_class("bg-gray-900")
+///         .head(Metadata {
+///             title_template: Some("%s | Acme".into()),
+///             viewport: Some("width=device-width, initial-scale=1".into()),
+///             ..Metadata::default()
+///         })
+///         .stylesheet("/index.css")
+///         .mount::<App>(());
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Document {
+    /// The language of the document, such as `en`.
+    pub lang: Option<String>,
+    /// The text direction, `ltr`, `rtl` or `auto`.
+    pub dir: Option<String>,
+    /// The classes of `<html>`.
+    pub html_class: Option<String>,
+    /// The classes of `<body>`.
+    pub body_class: Option<String>,
+    /// The default metadata of every page.
+    pub head: Metadata,
+    /// The id of the element the app is mounted in. The app is mounted in `<body>` when empty.
+    pub root_id: String,
+    /// The stylesheets linked from `<head>`.
+    pub stylesheets: Vec<String>,
+    /// The module scripts loading the app, such as the glue generated by `wasm-bindgen`.
+    pub scripts: Vec<String>,
+    /// Where the scripts are placed.
+    pub script_placement: ScriptPlacement,
+    /// The CSP nonce of the response, added to the scripts and provided to the app.
+    pub nonce: Option<String>,
+}
+
+impl Default for Document {
+    fn default() -> Self {
+        Self {
+            lang: None,
+            dir: None,
+            html_class: None,
+            body_class: None,
+            head: Metadata::default(),
+            root_id: "root".to_string(),
+            stylesheets: Vec::new(),
+            scripts: Vec::new(),
+            script_placement: ScriptPlacement::default(),
+            nonce: None,
+        }
+    }
+}
+
+impl Document {
+    /// Creates a document mounting the app in `<div id="root">`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the language of the document.
+    pub fn lang(mut self, lang: impl Into<String>) -> Self {
+        self.lang = Some(lang.into());
+        self
+    }
+
+    /// Sets the text direction.
+    pub fn dir(mut self, dir: impl Into<String>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// Sets the classes of `<html>`.
+    pub fn html_class(mut self, class: impl Into<String>) -> Self {
+        self.html_class = Some(class.into());
+        self
+    }
+
+    /// Sets the classes of `<body>`.
+    pub fn body_class(mut self, class: impl Into<String>) -> Self {
+        self.body_class = Some(class.into());
+        self
+    }
+
+    /// Sets the default metadata of every page.
+    pub fn head(mut self, head: Metadata) -> Self {
+        self.head = head;
+        self
+    }
+
+    /// Sets the id of the element the app is mounted in.
+    pub fn root_id(mut self, root_id: impl Into<String>) -> Self {
+        self.root_id = root_id.into();
+        self
+    }
+
+    /// Links a stylesheet from `<head>`.
+    pub fn stylesheet(mut self, href: impl Into<String>) -> Self {
+        self.stylesheets.push(href.into());
+        self
+    }
+
+    /// Adds a module script loading the app.
+    pub fn script(mut self, src: impl Into<String>) -> Self {
+        self.scripts.push(src.into());
+        self
+    }
+
+    /// Sets where the scripts are placed.
+    pub fn script_placement(mut self, placement: ScriptPlacement) -> Self {
+        self.script_placement = placement;
+        self
+    }
+
+    /// Sets the CSP nonce of the response.
+    pub fn nonce(mut self, nonce: impl Into<String>) -> Self {
+        self.nonce = Some(nonce.into());
+        self
+    }
+
+    /// Returns the element the app is mounted in, creating it at the end of `<body>` if needed.
+    fn root_element(&self) -> Option<Element> {
+        let document = window().and_then(|win| win.document())?;
+        let body: Element = document.body()?.into();
+        if self.root_id.is_empty() {
+            return Some(body);
+        }
+        if let Some(root) = document.get_element_by_id(&self.root_id) {
+            return Some(root);
+        }
+
+        let root = document.create_element("div").ok()?;
+        root.set_id(&self.root_id);
+        match body.append_child(&root) {
+            Ok(_) => Some(root),
+            Err(err) => {
+                log(&format!("Error creating root element {}: {:?}", self.root_id, err).into());
+                None
+            }
+        }
+    }
+
+    /// Mounts the app in the browser, inside the root element of the document.
+    ///
+    /// The attributes of `<html>` and `<body>` are set and the default metadata is provided to
+    /// every page. With the `hydration` feature, a page rendered by [`Document::render`] is
+    /// hydrated. Otherwise, any content of the root element is replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `props` - The properties of the app component.
+    pub fn mount<COMP>(self, props: COMP::Properties)
+    where
+        COMP: BaseComponent,
+    {
+        let root = match self.root_element() {
+            Some(root) => root,
+            None => {
+                log(&"Failed to find the root element of the document".into());
+                return;
+            }
+        };
+        let props = DocumentRootProps {
+            document: Rc::new(self),
+            collector: None,
+            children: VChild::<COMP>::new(props, None).into(),
+        };
+
+        #[cfg(feature = "hydration")]
+        if root.has_child_nodes() {
+            crate::Renderer::<DocumentRoot>::with_root_and_props(root, props).hydrate();
+            return;
+        }
+        // Pages rendered by the server or at build time are replaced by the app.
+        root.set_inner_html("");
+        crate::Renderer::<DocumentRoot>::with_root_and_props(root, props).render();
+    }
+
+    /// Renders the complete HTML page from the rendered head elements and body.
+    ///
+    /// # Arguments
+    ///
+    /// * `head` - The head elements, such as those rendered by a [`HeadCollector`].
+    /// * `body` - The rendered app.
+    ///
+    /// # Example
+    /// ```
+    /// use next_rs::document::Document;
+    ///
+    /// let document = Document::new().lang("en").script("/app.js");
+    /// let html = document.to_html("<title>Home</title>", "<h1>Home</h1>");
+    ///
+    /// assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"en\">"));
+    /// assert!(html.contains("<div id=\"root\"><h1>Home</h1></div>"));
+    /// assert!(html.contains("<script type=\"module\" src=\"/app.js\"></script>\n</body>"));
+    /// ```
+    pub fn to_html(&self, head: &str, body: &str) -> String {
+        let html = attribute_list(&[
+            ("lang", &self.lang),
+            ("dir", &self.dir),
+            ("class", &self.html_class),
+        ]);
+        let body_attributes = attribute_list(&[("class", &self.body_class)]);
+
+        self.page(&html, &body_attributes, head, body)
+    }
+
+    /// Renders the complete HTML page with the given attributes of `<html>` and `<body>`.
+    fn page(
+        &self,
+        html: &[(String, String)],
+        body_attributes: &[(String, String)],
+        head: &str,
+        body: &str,
+    ) -> String {
+        let attributes = |attributes: &[(String, String)]| -> String {
+            attributes
+                .iter()
+                .map(|(name, value)| format!(" {}=\"{}\"", name, escape_attribute(value)))
+                .collect()
+        };
+        let nonce = attributes(&attribute_list(&[("nonce", &self.nonce)]));
+        let scripts: String = self
+            .scripts
+            .iter()
+            .map(|src| {
+                format!(
+                    "<script type=\"module\" src=\"{}\"{}></script>\n",
+                    escape_attribute(src),
+                    nonce
+                )
+            })
+            .collect();
+        let stylesheets: String = self
+            .stylesheets
+            .iter()
+            .map(|href| {
+                format!(
+                    "<link rel=\"stylesheet\" href=\"{}\">\n",
+                    escape_attribute(href)
+                )
+            })
+            .collect();
+        let (head_scripts, body_scripts) = match self.script_placement {
+            ScriptPlacement::Head => (scripts.as_str(), ""),
+            ScriptPlacement::BodyEnd => ("", scripts.as_str()),
+        };
+        let root = match self.root_id.is_empty() {
+            true => format!("{}\n", body),
+            false => format!(
+                "<div id=\"{}\">{}</div>\n",
+                escape_attribute(&self.root_id),
+                body
+            ),
+        };
+
+        format!(
+            "<!DOCTYPE html>\n<html{}>\n<head>\n{}\n{}{}</head>\n<body{}>\n{}{}</body>\n</html>\n",
+            attributes(html),
+            head,
+            stylesheets,
+            head_scripts,
+            attributes(body_attributes),
+            root,
+            body_scripts
+        )
+    }
+
+    /// Renders the complete HTML page of the app on the server or at build time, with the head
+    /// elements of the rendered page and the attributes its [`HtmlAttributes`] and
+    /// [`BodyAttributes`] components set.
+    ///
+    /// # Arguments
+    ///
+    /// * `props` - The properties of the app component.
+    #[cfg(feature = "ssr")]
+    pub async fn render<COMP>(&self, props: COMP::Properties) -> String
+    where
+        COMP: BaseComponent,
+    {
+        let collector = HeadCollector::new();
+        let props = DocumentRootProps {
+            document: Rc::new(self.clone()),
+            collector: Some(collector.clone()),
+            children: VChild::<COMP>::new(props, None).into(),
+        };
+        let body = yew::LocalServerRenderer::<DocumentRoot>::with_props(props)
+            .render()
+            .await;
+        let head = collector.render().await;
+
+        self.page(
+            &collector.attributes("html"),
+            &collector.attributes("body"),
+            &head,
+            &body,
+        )
+    }
+}
+
+/// Returns the attributes that are set, by name.
+fn attribute_list(attributes: &[(&str, &Option<String>)]) -> Vec<(String, String)> {
+    attributes
+        .iter()
+        .filter_map(|(name, value)| {
+            value
+                .as_ref()
+                .map(|value| (name.to_string(), value.clone()))
+        })
+        .collect()
+}
+
+/// Escapes the characters of an attribute value.
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Props for [`DocumentRoot`].
+#[derive(Properties, Clone, PartialEq)]
+struct DocumentRootProps {
+    document: Rc<Document>,
+    collector: Option<HeadCollector>,
+    children: Html,
+}
+
+/// Renders the app with the attributes, metadata and nonce of its document.
+#[func]
+fn DocumentRoot(props: &DocumentRootProps) -> Html {
+    let document = &props.document;
+    let value =
+        |value: &Option<String>| -> AttrValue { value.clone().map(Into::into).unwrap_or_default() };
+
+    rsx! {
+        <HeadProvider collector={props.collector.clone()}>
+            <HtmlAttributes
+                lang={value(&document.lang)}
+                dir={value(&document.dir)}
+                class={value(&document.html_class)}
+            />
+            <BodyAttributes class={value(&document.body_class)} />
+            <CspProvider nonce={value(&document.nonce)}>
+                <MetadataProvider defaults={document.head.clone()}>
+                    {props.children.clone()}
+                </MetadataProvider>
+            </CspProvider>
+        </HeadProvider>
+    }
+}
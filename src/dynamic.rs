@@ -0,0 +1,476 @@
+use crate::log;
+use crate::prelude::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::js_sys::{Function, Promise, Reflect};
+use web_sys::wasm_bindgen::prelude::wasm_bindgen;
+use web_sys::wasm_bindgen::{JsCast, JsValue};
+use web_sys::Element;
+
+#[wasm_bindgen(
+    wasm_bindgen = web_sys::wasm_bindgen,
+    inline_js = r###"
+export function importChunk(src) {
+    return import(src).then(async (module) => {
+        if (typeof module.default === "function") {
+            await module.default();
+        }
+        return module;
+    });
+}
+"###
+)]
+extern "C" {
+    // Imports a wasm-bindgen module built with `--target web` and runs its initializer once.
+    #[wasm_bindgen(js_name = importChunk)]
+    fn import_chunk(src: &str) -> Promise;
+}
+
+thread_local! {
+    // Chunks that are loading or loaded, keyed by the URL of their JS glue.
+    static CHUNKS: RefCell<HashMap<&'static str, Promise>> = RefCell::new(HashMap::new());
+
+    // Lazy routes registered by the router, keyed by route.
+    static LAZY_ROUTES: RefCell<HashMap<&'static str, LazyRoute>> = RefCell::new(HashMap::new());
+}
+
+/// The content displayed while a chunk is being fetched, set for every lazy route with the
+/// `lazy_fallback` prop of the router.
+#[derive(Clone, PartialEq, Default)]
+pub struct LazyFallback(pub Html);
+
+/// A route whose component lives in a separately built wasm chunk.
+///
+/// The chunk is a wasm-bindgen module built with `--target web` that exports a function (`mount`
+/// by default) taking the element to render into. The function may return another function, which
+/// is called to tear the chunk down when the route is left.
+///
+/// # Example
+/// ```rust,no_run
+/// use next_rs::prelude::*;
+/// use next_rs::dynamic::LazyRoute;
+/// use next_rs::lazy_route;
+/// use next_rs::router::{NextRouter, Switch};
+///
+/// const ADMIN: LazyRoute = lazy_route!("/admin" => "/chunks/admin.js");
+///
+/// pub fn switch(route: String) -> Html {
+///     match route.as_str() {
+///         "/admin" => ADMIN.view(),
+///         _ => rsx! {<></>},
+///     }
+/// }
+///
+/// #[func]
+/// pub fn App() -> Html {
+///     rsx! {
+///         <NextRouter lazy_routes={vec![ADMIN]} lazy_fallback={rsx! {<p>{"Loading..."}</p>}}>
+///             <Switch render={switch} />
+///         </NextRouter>
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LazyRoute {
+    /// The route served by the chunk.
+    pub route: &'static str,
+    /// The URL of the chunk's JS glue.
+    pub src: &'static str,
+    /// The name of the exported mount function.
+    pub export: &'static str,
+}
+
+impl LazyRoute {
+    /// Creates a lazy route that mounts the chunk with its `mount` export.
+    pub const fn new(route: &'static str, src: &'static str) -> Self {
+        Self {
+            route,
+            src,
+            export: "mount",
+        }
+    }
+
+    /// Uses another exported function to mount the chunk.
+    pub const fn with_export(self, export: &'static str) -> Self {
+        Self { export, ..self }
+    }
+
+    /// Renders the chunk, showing the `lazy_fallback` of the router while it is being fetched.
+    pub fn view(&self) -> Html {
+        rsx! {
+            <Dynamic src={self.src} export={self.export} />
+        }
+    }
+
+    /// Renders the chunk, showing `fallback` instead of the one of the router while it is being
+    /// fetched.
+    pub fn render(&self, fallback: Html) -> Html {
+        rsx! {
+            <Dynamic src={self.src} export={self.export} fallback={fallback} />
+        }
+    }
+}
+
+/// Registers lazy routes so that [`crate::Router::prefetch`] can warm their chunks.
+///
+/// This is called by the router with its `lazy_routes` prop, so it is rarely needed directly.
+pub fn register_lazy_routes(routes: &[LazyRoute]) {
+    LAZY_ROUTES.with(|lazy_routes| {
+        let mut lazy_routes = lazy_routes.borrow_mut();
+        for route in routes {
+            lazy_routes.insert(route.route, *route);
+        }
+    });
+}
+
+/// Returns the lazy route registered for `route`, if any.
+pub fn lazy_route_for(route: &str) -> Option<LazyRoute> {
+    let route = route.split(['?', '#']).next().unwrap_or_default();
+    LAZY_ROUTES.with(|lazy_routes| lazy_routes.borrow().get(route).copied())
+}
+
+/// Starts loading the chunk registered for `route` without mounting it.
+///
+/// # Returns
+///
+/// (bool): `true` if a chunk is registered for the route.
+pub fn prefetch_chunk(route: &str) -> bool {
+    match lazy_route_for(route) {
+        Some(lazy_route) => {
+            spawn_local(async move {
+                if let Err(err) = load_chunk(lazy_route.src).await {
+                    log(&format!("Error prefetching chunk: {:?}", err).into());
+                }
+            });
+            true
+        }
+        None => false,
+    }
+}
+
+/// Loads the chunk at `src`, reusing the pending or finished load of previous calls.
+///
+/// # Returns
+///
+/// A `Result` containing the module namespace of the chunk on success.
+pub async fn load_chunk(src: &'static str) -> Result<JsValue, JsValue> {
+    let promise = match CHUNKS.with(|chunks| chunks.borrow().get(src).cloned()) {
+        Some(promise) => promise,
+        None => {
+            let promise = import_chunk(src);
+            CHUNKS.with(|chunks| chunks.borrow_mut().insert(src, promise.clone()));
+            promise
+        }
+    };
+
+    let result = JsFuture::from(promise).await;
+    if result.is_err() {
+        // Forget failed loads so the next navigation can retry.
+        CHUNKS.with(|chunks| chunks.borrow_mut().remove(src));
+    }
+    result
+}
+
+/// Properties for the Dynamic component.
+#[derive(Properties, Clone, PartialEq)]
+pub struct DynamicProps {
+    /// The URL of the chunk's JS glue.
+    pub src: &'static str,
+
+    /// The name of the exported function mounting the chunk into an element.
+    #[prop_or("mount")]
+    pub export: &'static str,
+
+    /// The content displayed while the chunk is being fetched. Defaults to the `lazy_fallback` of
+    /// the router.
+    #[prop_or_default]
+    pub fallback: Option<Html>,
+
+    /// The content displayed if the chunk fails to load.
+    #[prop_or_default]
+    pub error: Html,
+
+    /// The CSS class for the element the chunk is mounted into.
+    #[prop_or_default]
+    pub class: &'static str,
+}
+
+/// Renders a component from a separately built wasm chunk, loading it on first use.
+///
+/// # Arguments
+/// * `props` - The properties of the component.
+///
+/// # Returns
+/// (Html): An HTML representation of the chunk's mount point.
+///
+/// # Examples
+/// ```rust,no_run
+/// use next_rs::prelude::*;
+/// use next_rs::dynamic;
+///
+/// #[func]
+/// pub fn Dashboard() -> Html {
+///     rsx! {
+///         <div>
+///             {dynamic!("/chunks/chart.js", rsx! {<p>{"Loading chart..."}</p>})}
+///         </div>
+///     }
+/// }
+/// ```
+#[func]
+pub fn Dynamic(props: &DynamicProps) -> Html {
+    let container_ref = use_node_ref();
+    let router_fallback = use_context::<LazyFallback>();
+    // The loaded module and the failed load are keyed by chunk, so that changing `src` shows the
+    // fallback again instead of the previous chunk.
+    let loaded = use_state(|| None::<(&'static str, JsValue)>);
+    let failed = use_state(|| None::<&'static str>);
+
+    {
+        let loaded = loaded.clone();
+        let failed = failed.clone();
+        use_effect_with(props.src, move |src| {
+            let src = *src;
+            spawn_local(async move {
+                match load_chunk(src).await {
+                    Ok(module) => loaded.set(Some((src, module))),
+                    Err(err) => {
+                        log(&format!("Error loading chunk {}: {:?}", src, err).into());
+                        failed.set(Some(src));
+                    }
+                }
+            });
+        });
+    }
+
+    let module = loaded
+        .as_ref()
+        .filter(|(src, _)| *src == props.src)
+        .map(|(_, module)| module.clone());
+
+    {
+        let container_ref = container_ref.clone();
+        let export = props.export;
+        use_effect_with(module.clone(), move |module| {
+            let mut cleanup = None;
+            if let (Some(module), Some(container)) = (module, container_ref.cast::<Element>()) {
+                let mount = Reflect::get(module, &export.into())
+                    .ok()
+                    .and_then(|mount| mount.dyn_into::<Function>().ok());
+                match mount.map(|mount| mount.call1(&JsValue::NULL, &container)) {
+                    Some(Ok(unmount)) => cleanup = unmount.dyn_into::<Function>().ok(),
+                    Some(Err(err)) => log(&format!("Error mounting chunk: {:?}", err).into()),
+                    None => log(&format!("Chunk does not export `{}`", export).into()),
+                }
+            }
+
+            move || {
+                if let Some(cleanup) = cleanup {
+                    let _ = cleanup.call0(&JsValue::NULL);
+                }
+            }
+        });
+    }
+
+    let status = chunk_status(props, *failed, module.is_some(), router_fallback);
+
+    rsx! {
+        <>
+            {status}
+            <div ref={container_ref} class={props.class}></div>
+        </>
+    }
+}
+
+/// Returns what a [`Dynamic`] shows next to its mount point: its `error` if the chunk failed to
+/// load, its fallback while the chunk loads, and nothing once it is mounted.
+///
+/// # Arguments
+///
+/// * `props` - The properties of the component.
+/// * `failed` - The chunk whose load failed, if any.
+/// * `loaded` - Whether the chunk of `props.src` is loaded.
+/// * `router_fallback` - The `lazy_fallback` of the router, if any.
+fn chunk_status(
+    props: &DynamicProps,
+    failed: Option<&'static str>,
+    loaded: bool,
+    router_fallback: Option<LazyFallback>,
+) -> Html {
+    if failed == Some(props.src) {
+        props.error.clone()
+    } else if !loaded {
+        props
+            .fallback
+            .clone()
+            .or_else(|| router_fallback.map(|fallback| fallback.0))
+            .unwrap_or_default()
+    } else {
+        Html::default()
+    }
+}
+
+/// Renders the chunk at `src` with its `mount` export, showing `fallback` while it is being
+/// fetched, or else the `lazy_fallback` of the router.
+///
+/// This is what [`dynamic!`] expands to, for chunks that aren't served as a route.
+///
+/// # Example
+/// ```rust
+/// use next_rs::dynamic::render_chunk;
+/// use next_rs::prelude::*;
+///
+/// let chart = render_chunk("/chunks/chart.js", Some(rsx! {<p>{"Loading chart..."}</p>}));
+/// ```
+pub fn render_chunk(src: &'static str, fallback: Option<Html>) -> Html {
+    rsx! {
+        <Dynamic src={src} fallback={fallback} />
+    }
+}
+
+/// Creates a [`LazyRoute`] from a route and the URL of its chunk.
+///
+/// # Example
+/// ```rust,no_run
+/// use next_rs::dynamic::LazyRoute;
+/// use next_rs::lazy_route;
+///
+/// const ADMIN: LazyRoute = lazy_route!("/admin" => "/chunks/admin.js");
+/// const REPORTS: LazyRoute = lazy_route!("/reports" => "/chunks/admin.js", "mount_reports");
+/// ```
+#[macro_export]
+macro_rules! lazy_route {
+    ($route:expr => $src:expr) => {
+        $crate::dynamic::LazyRoute::new($route, $src)
+    };
+    ($route:expr => $src:expr, $export:expr) => {
+        $crate::dynamic::LazyRoute::new($route, $src).with_export($export)
+    };
+}
+
+/// Renders a [`Dynamic`] chunk, with an optional fallback shown while it loads instead of the one
+/// of the router.
+///
+/// See [`render_chunk`], which it expands to.
+#[macro_export]
+macro_rules! dynamic {
+    ($src:expr) => {
+        $crate::dynamic::render_chunk($src, None)
+    };
+    ($src:expr, $fallback:expr) => {
+        $crate::dynamic::render_chunk($src, Some($fallback))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn props(src: &'static str, fallback: Option<Html>) -> DynamicProps {
+        DynamicProps {
+            src,
+            export: "mount",
+            fallback,
+            error: rsx! { <p>{"Failed"}</p> },
+            class: "",
+        }
+    }
+
+    #[test]
+    fn lazy_route_macro_builds_the_route() {
+        const ADMIN: LazyRoute = lazy_route!("/admin" => "/chunks/admin.js");
+        const REPORTS: LazyRoute = lazy_route!("/reports" => "/chunks/admin.js", "mount_reports");
+
+        assert_eq!(ADMIN, LazyRoute::new("/admin", "/chunks/admin.js"));
+        assert_eq!(ADMIN.export, "mount");
+        assert_eq!(
+            REPORTS,
+            LazyRoute {
+                route: "/reports",
+                src: "/chunks/admin.js",
+                export: "mount_reports",
+            }
+        );
+    }
+
+    #[test]
+    fn lazy_routes_are_found_without_query_or_fragment() {
+        register_lazy_routes(&[lazy_route!("/settings" => "/chunks/settings.js")]);
+
+        assert_eq!(
+            lazy_route_for("/settings?tab=billing#plan").map(|route| route.src),
+            Some("/chunks/settings.js")
+        );
+        assert_eq!(lazy_route_for("/settings/billing"), None);
+    }
+
+    #[test]
+    fn dynamic_macro_renders_the_chunk() {
+        let fallback = rsx! { <p>{"Loading chart..."}</p> };
+
+        assert_eq!(
+            dynamic!("/chunks/chart.js"),
+            rsx! { <Dynamic src="/chunks/chart.js" /> }
+        );
+        assert_eq!(
+            dynamic!("/chunks/chart.js", fallback.clone()),
+            rsx! { <Dynamic src="/chunks/chart.js" fallback={fallback} /> }
+        );
+        assert_eq!(
+            lazy_route!("/charts" => "/chunks/chart.js", "mount_chart").view(),
+            rsx! { <Dynamic src="/chunks/chart.js" export="mount_chart" /> }
+        );
+    }
+
+    #[test]
+    fn fallback_is_shown_while_the_chunk_loads() {
+        let router_fallback = LazyFallback(rsx! { <p>{"Loading..."}</p> });
+        let fallback = rsx! { <p>{"Loading chart..."}</p> };
+
+        assert_eq!(
+            chunk_status(
+                &props("/chunks/chart.js", Some(fallback.clone())),
+                None,
+                false,
+                Some(router_fallback.clone()),
+            ),
+            fallback
+        );
+        assert_eq!(
+            chunk_status(
+                &props("/chunks/chart.js", None),
+                None,
+                false,
+                Some(router_fallback.clone()),
+            ),
+            router_fallback.0
+        );
+        assert_eq!(
+            chunk_status(
+                &props("/chunks/chart.js", None),
+                None,
+                true,
+                Some(router_fallback)
+            ),
+            Html::default()
+        );
+    }
+
+    #[test]
+    fn error_is_only_shown_for_the_failed_chunk() {
+        let props = props(
+            "/chunks/chart.js",
+            Some(rsx! { <p>{"Loading chart..."}</p> }),
+        );
+
+        assert_eq!(
+            chunk_status(&props, Some("/chunks/chart.js"), false, None),
+            props.error
+        );
+        assert_eq!(
+            chunk_status(&props, Some("/chunks/admin.js"), false, None),
+            props.fallback.clone().unwrap()
+        );
+    }
+}
@@ -0,0 +1,738 @@
+use crate::head::Head;
+use crate::metadata::{Alternate, Metadata};
+use crate::prelude::*;
+use crate::sitemap::escape_xml;
+use serde::Serialize;
+
+/// The formats a [`Feed`] can be serialized to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    /// RSS 2.0, served as `feed.xml`.
+    Rss,
+    /// Atom, served as `atom.xml`.
+    Atom,
+    /// JSON Feed 1.1, served as `feed.json`.
+    Json,
+}
+
+impl FeedFormat {
+    /// Returns the MIME type of the format.
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "application/rss+xml",
+            FeedFormat::Atom => "application/atom+xml",
+            FeedFormat::Json => "application/feed+json",
+        }
+    }
+
+    /// Returns the name of the file the feed is served as.
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "feed.xml",
+            FeedFormat::Atom => "atom.xml",
+            FeedFormat::Json => "feed.json",
+        }
+    }
+}
+
+/// The author of a feed or of an entry.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct Author {
+    /// The name of the author.
+    pub name: String,
+    /// The email of the author, required by RSS to credit an author.
+    #[serde(skip)]
+    pub email: Option<String>,
+    /// The URL of the website of the author.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl Author {
+    /// Creates an author from their name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the email of the author.
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    /// Sets the URL of the website of the author.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+}
+
+/// An entry of a feed, such as a blog post.
+///
+/// Dates are written in RFC 3339, such as `2024-05-01T10:00:00Z`, and converted to RFC 822 for
+/// RSS.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Entry {
+    /// The unique id of the entry. Defaults to its link.
+    pub id: Option<String>,
+    /// The title of the entry.
+    pub title: String,
+    /// The absolute URL of the entry.
+    pub link: String,
+    /// A plain text summary of the entry.
+    pub summary: Option<String>,
+    /// The full content of the entry, in HTML.
+    pub content: Option<String>,
+    /// The date the entry was published.
+    pub published: Option<String>,
+    /// The date the entry was last updated.
+    pub updated: Option<String>,
+    /// The authors of the entry.
+    pub authors: Vec<Author>,
+    /// The categories of the entry.
+    pub categories: Vec<String>,
+    /// The absolute URL of the main image of the entry.
+    pub image: Option<String>,
+}
+
+impl Entry {
+    /// Creates an entry from its title and absolute URL.
+    pub fn new(title: impl Into<String>, link: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            link: link.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the unique id of the entry.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the plain text summary of the entry.
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = Some(summary.into());
+        self
+    }
+
+    /// Sets the HTML content of the entry.
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    /// Sets the date the entry was published.
+    pub fn published(mut self, date: impl Into<String>) -> Self {
+        self.published = Some(date.into());
+        self
+    }
+
+    /// Sets the date the entry was last updated.
+    pub fn updated(mut self, date: impl Into<String>) -> Self {
+        self.updated = Some(date.into());
+        self
+    }
+
+    /// Adds an author to the entry.
+    pub fn author(mut self, author: Author) -> Self {
+        self.authors.push(author);
+        self
+    }
+
+    /// Adds a category to the entry.
+    pub fn category(mut self, category: impl Into<String>) -> Self {
+        self.categories.push(category.into());
+        self
+    }
+
+    /// Sets the main image of the entry.
+    pub fn image(mut self, image: impl Into<String>) -> Self {
+        self.image = Some(image.into());
+        self
+    }
+
+    /// Returns the unique id of the entry.
+    fn entry_id(&self) -> &str {
+        self.id.as_deref().unwrap_or(&self.link)
+    }
+
+    /// Returns the date the entry was last updated, or published.
+    fn last_updated(&self) -> Option<&str> {
+        self.updated.as_deref().or(self.published.as_deref())
+    }
+}
+
+/// A feed of the entries of a site, such as the posts of a blog.
+///
+/// # Example
+/// ```
+/// use next_rs::feed::{Author, Entry, Feed};
+///
+/// let feed = Feed::new("Acme Blog", "https://acme.dev/blog")
+///     .description("News from Acme")
+///     .author(Author::new("Ada").email("ada@acme.dev"))
+///     .entry(
+///         Entry::new("Hello", "https://acme.dev/blog/hello")
+///             .summary("Our first post")
+///             .published("2024-05-01T10:00:00Z"),
+///     );
+///
+/// assert!(feed
+///     .to_rss()
+///     .contains("<pubDate>Wed, 01 May 2024 10:00:00 +0000</pubDate>"));
+/// assert!(feed.to_atom().contains("<updated>2024-05-01T10:00:00Z</updated>"));
+/// assert!(feed.to_json().unwrap().contains("\"date_published\": \"2024-05-01T10:00:00Z\""));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Feed {
+    /// The title of the feed.
+    pub title: String,
+    /// The absolute URL of the site, under which the feed files are served.
+    pub link: String,
+    /// The description of the feed.
+    pub description: Option<String>,
+    /// The language of the feed, such as `en`.
+    pub language: Option<String>,
+    /// The date the feed was last updated. Defaults to the latest date of its entries.
+    pub updated: Option<String>,
+    /// The authors of the feed. Atom falls back to the title of the feed as its author when
+    /// neither the feed nor some of its entries have one.
+    pub authors: Vec<Author>,
+    /// The absolute URL of the image of the feed.
+    pub image: Option<String>,
+    /// The entries of the feed, latest first.
+    pub entries: Vec<Entry>,
+}
+
+impl Feed {
+    /// Creates an empty feed for a site.
+    pub fn new(title: impl Into<String>, link: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            link: link.into().trim_end_matches('/').to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the description of the feed.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the language of the feed.
+    pub fn language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Sets the date the feed was last updated.
+    pub fn updated(mut self, date: impl Into<String>) -> Self {
+        self.updated = Some(date.into());
+        self
+    }
+
+    /// Adds an author to the feed.
+    pub fn author(mut self, author: Author) -> Self {
+        self.authors.push(author);
+        self
+    }
+
+    /// Sets the image of the feed.
+    pub fn image(mut self, image: impl Into<String>) -> Self {
+        self.image = Some(image.into());
+        self
+    }
+
+    /// Adds an entry to the feed.
+    pub fn entry(mut self, entry: Entry) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    /// Returns the absolute URL the feed is served at in a format.
+    pub fn url(&self, format: FeedFormat) -> String {
+        format!("{}/{}", self.link, format.file_name())
+    }
+
+    /// Returns the date the feed was last updated.
+    fn last_updated(&self) -> Option<String> {
+        self.updated.clone().or_else(|| {
+            self.entries
+                .iter()
+                .filter_map(Entry::last_updated)
+                .max()
+                .map(str::to_string)
+        })
+    }
+
+    /// Serializes the feed to RSS 2.0.
+    pub fn to_rss(&self) -> String {
+        let mut rss = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\" \
+             xmlns:content=\"http://purl.org/rss/1.0/modules/content/\" \
+             xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n<channel>\n",
+        );
+        rss.push_str(&element("title", &self.title));
+        rss.push_str(&element("link", &self.link));
+        rss.push_str(&format!(
+            "<atom:link href=\"{}\" rel=\"self\" type=\"{}\" />\n",
+            escape_xml(&self.url(FeedFormat::Rss)),
+            FeedFormat::Rss.media_type()
+        ));
+        rss.push_str(&element(
+            "description",
+            self.description.as_deref().unwrap_or(&self.title),
+        ));
+        rss.push_str(&optional_element("language", &self.language));
+        if let Some(updated) = self.last_updated() {
+            rss.push_str(&element("lastBuildDate", &rfc822(&updated)));
+        }
+        if let Some(image) = &self.image {
+            rss.push_str(&format!(
+                "<image>\n{}{}{}</image>\n",
+                element("url", image),
+                element("title", &self.title),
+                element("link", &self.link)
+            ));
+        }
+
+        for entry in &self.entries {
+            rss.push_str("<item>\n");
+            rss.push_str(&element("title", &entry.title));
+            rss.push_str(&element("link", &entry.link));
+            rss.push_str(&format!(
+                "<guid isPermaLink=\"{}\">{}</guid>\n",
+                entry.id.is_none(),
+                escape_xml(entry.entry_id())
+            ));
+            rss.push_str(&optional_element("description", &entry.summary));
+            if let Some(content) = &entry.content {
+                rss.push_str(&format!(
+                    "<content:encoded>{}</content:encoded>\n",
+                    cdata(content)
+                ));
+            }
+            if let Some(published) = &entry.published {
+                rss.push_str(&element("pubDate", &rfc822(published)));
+            }
+            for author in &entry.authors {
+                match &author.email {
+                    Some(email) => {
+                        rss.push_str(&element("author", &format!("{} ({})", email, author.name)))
+                    }
+                    None => rss.push_str(&element("dc:creator", &author.name)),
+                }
+            }
+            for category in &entry.categories {
+                rss.push_str(&element("category", category));
+            }
+            if let Some(image) = &entry.image {
+                rss.push_str(&format!(
+                    "<enclosure url=\"{}\" type=\"{}\" length=\"0\" />\n",
+                    escape_xml(image),
+                    image_media_type(image)
+                ));
+            }
+            rss.push_str("</item>\n");
+        }
+
+        rss.push_str("</channel>\n</rss>\n");
+        rss
+    }
+
+    /// Serializes the feed to Atom.
+    ///
+    /// Atom requires an author and an `<updated>` date on the feed and on each entry:
+    /// - the title of the feed stands in for its author when the feed has no author and some
+    ///   entries have none;
+    /// - entries without a date are dated like the feed, so set [`Feed::updated`] or date at
+    ///   least one entry for a valid feed.
+    pub fn to_atom(&self) -> String {
+        let mut atom = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <feed xmlns=\"http://www.w3.org/2005/Atom\"",
+        );
+        if let Some(language) = &self.language {
+            atom.push_str(&format!(" xml:lang=\"{}\"", escape_xml(language)));
+        }
+        atom.push_str(">\n");
+        atom.push_str(&element("id", &self.link));
+        atom.push_str(&element("title", &self.title));
+        atom.push_str(&optional_element("subtitle", &self.description));
+        atom.push_str(&format!(
+            "<link href=\"{}\" />\n<link href=\"{}\" rel=\"self\" type=\"{}\" />\n",
+            escape_xml(&self.link),
+            escape_xml(&self.url(FeedFormat::Atom)),
+            FeedFormat::Atom.media_type()
+        ));
+        let updated = self.last_updated();
+        atom.push_str(&optional_element("updated", &updated));
+        atom.push_str(&optional_element("logo", &self.image));
+        for author in &self.authors {
+            atom.push_str(&atom_author(author));
+        }
+        if self.authors.is_empty() && self.entries.iter().any(|entry| entry.authors.is_empty()) {
+            atom.push_str(&atom_author(&Author::new(self.title.clone())));
+        }
+
+        for entry in &self.entries {
+            atom.push_str("<entry>\n");
+            atom.push_str(&element("id", entry.entry_id()));
+            atom.push_str(&element("title", &entry.title));
+            atom.push_str(&format!(
+                "<link href=\"{}\" rel=\"alternate\" />\n",
+                escape_xml(&entry.link)
+            ));
+            if let Some(updated) = entry.last_updated().or(updated.as_deref()) {
+                atom.push_str(&element("updated", updated));
+            }
+            atom.push_str(&optional_element("published", &entry.published));
+            atom.push_str(&optional_element("summary", &entry.summary));
+            if let Some(content) = &entry.content {
+                atom.push_str(&format!(
+                    "<content type=\"html\">{}</content>\n",
+                    escape_xml(content)
+                ));
+            }
+            for author in &entry.authors {
+                atom.push_str(&atom_author(author));
+            }
+            for category in &entry.categories {
+                atom.push_str(&format!("<category term=\"{}\" />\n", escape_xml(category)));
+            }
+            atom.push_str("</entry>\n");
+        }
+
+        atom.push_str("</feed>\n");
+        atom
+    }
+
+    /// Serializes the feed to pretty-printed JSON Feed 1.1.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        let feed = JsonFeed {
+            version: "https://jsonfeed.org/version/1.1",
+            title: &self.title,
+            home_page_url: &self.link,
+            feed_url: self.url(FeedFormat::Json),
+            description: self.description.as_deref(),
+            language: self.language.as_deref(),
+            icon: self.image.as_deref(),
+            authors: &self.authors,
+            items: self
+                .entries
+                .iter()
+                .map(|entry| JsonItem {
+                    id: entry.entry_id(),
+                    url: &entry.link,
+                    title: &entry.title,
+                    content_html: entry.content.as_deref(),
+                    // JSON Feed requires a content, so the summary stands in for a missing one.
+                    content_text: match entry.content {
+                        Some(_) => None,
+                        None => Some(entry.summary.as_deref().unwrap_or_default()),
+                    },
+                    summary: entry.summary.as_deref(),
+                    image: entry.image.as_deref(),
+                    date_published: entry.published.as_deref(),
+                    date_modified: entry.updated.as_deref(),
+                    authors: &entry.authors,
+                    tags: &entry.categories,
+                })
+                .collect(),
+        };
+        serde_json::to_string_pretty(&feed)
+    }
+
+    /// Writes the feed in every format into `dir`, such as the dist directory, from a build
+    /// script or a native build step.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the paths of the written files on success.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn write_to(
+        &self,
+        dir: impl AsRef<std::path::Path>,
+    ) -> std::io::Result<Vec<std::path::PathBuf>> {
+        let json = self
+            .to_json()
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        std::fs::create_dir_all(dir.as_ref())?;
+
+        let mut paths = Vec::new();
+        for (format, content) in [
+            (FeedFormat::Rss, self.to_rss()),
+            (FeedFormat::Atom, self.to_atom()),
+            (FeedFormat::Json, json),
+        ] {
+            let path = dir.as_ref().join(format.file_name());
+            std::fs::write(&path, content)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
+/// A JSON Feed 1.1 document.
+#[derive(Serialize)]
+struct JsonFeed<'a> {
+    version: &'static str,
+    title: &'a str,
+    home_page_url: &'a str,
+    feed_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon: Option<&'a str>,
+    #[serde(skip_serializing_if = "<[Author]>::is_empty")]
+    authors: &'a [Author],
+    items: Vec<JsonItem<'a>>,
+}
+
+/// An item of a JSON Feed document.
+#[derive(Serialize)]
+struct JsonItem<'a> {
+    id: &'a str,
+    url: &'a str,
+    title: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_html: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_text: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date_published: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date_modified: Option<&'a str>,
+    #[serde(skip_serializing_if = "<[Author]>::is_empty")]
+    authors: &'a [Author],
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    tags: &'a [String],
+}
+
+/// Renders an XML element with escaped text.
+fn element(name: &str, text: &str) -> String {
+    format!("<{}>{}</{}>\n", name, escape_xml(text), name)
+}
+
+/// Renders an XML element if the text is set.
+fn optional_element(name: &str, text: &Option<String>) -> String {
+    text.as_ref()
+        .map(|text| element(name, text))
+        .unwrap_or_default()
+}
+
+/// Renders the `<author>` element of an Atom feed or entry.
+fn atom_author(author: &Author) -> String {
+    format!(
+        "<author>\n{}{}{}</author>\n",
+        element("name", &author.name),
+        optional_element("email", &author.email),
+        optional_element("uri", &author.url)
+    )
+}
+
+/// Wraps HTML in a CDATA section, splitting any `]]>` it contains.
+fn cdata(html: &str) -> String {
+    format!("<![CDATA[{}]]>", html.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+/// Guesses the MIME type of an image from its extension.
+fn image_media_type(url: &str) -> &'static str {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    match path
+        .rsplit('.')
+        .next()
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        Some("png") => "image/png",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("avif") => "image/avif",
+        Some("svg") => "image/svg+xml",
+        _ => "image/jpeg",
+    }
+}
+
+/// Converts an RFC 3339 date, such as `2024-05-01T10:00:00Z`, to the RFC 822 format of RSS,
+/// such as `Wed, 01 May 2024 10:00:00 +0000`. Dates in another format are returned as is.
+fn rfc822(date: &str) -> String {
+    const DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let number = |range: std::ops::Range<usize>| date.get(range)?.parse::<i64>().ok();
+    let (year, month, day) = match (number(0..4), number(5..7), number(8..10)) {
+        (Some(year), Some(month @ 1..=12), Some(day @ 1..=31)) => (year, month, day),
+        _ => return date.to_string(),
+    };
+    let time = date.get(11..19).unwrap_or("00:00:00");
+    let zone = date
+        .get(19..)
+        .map(|rest| rest.trim_start_matches(|c: char| c == '.' || c.is_ascii_digit()))
+        .unwrap_or_default();
+    let offset = match zone {
+        "" | "Z" | "z" => "+0000".to_string(),
+        zone => zone.replace(':', ""),
+    };
+
+    // Sakamoto's algorithm, with 0 for Sunday.
+    let shifts = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let y = if month < 3 { year - 1 } else { year };
+    let weekday = (y + y / 4 - y / 100 + y / 400 + shifts[month as usize - 1] + day) % 7;
+
+    format!(
+        "{}, {:02} {} {} {} {}",
+        DAYS[weekday as usize],
+        day,
+        MONTHS[month as usize - 1],
+        year,
+        time,
+        offset
+    )
+}
+
+/// Renders the `<link rel="alternate">` tags letting browsers and feed readers discover the feed
+/// of the site, through `Head`.
+///
+/// # Arguments
+///
+/// * `feed` - The feed, whose files are served under its `link`.
+/// * `formats` - The formats the feed is served in.
+///
+/// # Example
+/// ```rust
+/// use next_rs::prelude::*;
+/// use next_rs::feed::{feed_links, Feed, FeedFormat};
+///
+/// #[func]
+/// pub fn Blog() -> Html {
+///     let feed = Feed::new("Acme Blog", "https://acme.dev/blog");
+///
+///     rsx! {
+///         <>
+///             {feed_links(&feed, &[FeedFormat::Rss, FeedFormat::Atom])}
+///             <h1>{"Blog"}</h1>
+///         </>
+///     }
+/// }
+/// ```
+pub fn feed_links(feed: &Feed, formats: &[FeedFormat]) -> Html {
+    let meta = Metadata {
+        alternates: formats
+            .iter()
+            .map(|format| Alternate {
+                href: feed.url(*format).into(),
+                media_type: Some(format.media_type().into()),
+                title: Some(feed.title.clone().into()),
+                ..Alternate::default()
+            })
+            .collect(),
+        ..Metadata::default()
+    };
+
+    rsx! {
+        <Head>{meta.to_html()}</Head>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc822_keeps_offsets_and_drops_fractional_seconds() {
+        assert_eq!(
+            rfc822("2024-05-01T10:00:00Z"),
+            "Wed, 01 May 2024 10:00:00 +0000"
+        );
+        assert_eq!(
+            rfc822("2024-05-01T10:00:00+02:00"),
+            "Wed, 01 May 2024 10:00:00 +0200"
+        );
+        assert_eq!(
+            rfc822("2024-02-29T23:59:59.123456-05:30"),
+            "Thu, 29 Feb 2024 23:59:59 -0530"
+        );
+        assert_eq!(
+            rfc822("2024-12-31T00:00:00.5z"),
+            "Tue, 31 Dec 2024 00:00:00 +0000"
+        );
+        assert_eq!(rfc822("2024-01-01"), "Mon, 01 Jan 2024 00:00:00 +0000");
+        assert_eq!(rfc822("yesterday"), "yesterday");
+        assert_eq!(rfc822("2024-13-01"), "2024-13-01");
+    }
+
+    #[test]
+    fn content_closing_cdata_is_split_or_escaped() {
+        let feed = Feed::new("Blog", "https://acme.dev/blog")
+            .entry(Entry::new("Hello", "https://acme.dev/blog/hello").content("<p>a]]>b</p>"));
+
+        assert!(feed
+            .to_rss()
+            .contains("<content:encoded><![CDATA[<p>a]]]]><![CDATA[>b</p>]]></content:encoded>"));
+        assert!(feed
+            .to_atom()
+            .contains("<content type=\"html\">&lt;p&gt;a]]&gt;b&lt;/p&gt;</content>"));
+        assert!(feed
+            .to_json()
+            .unwrap()
+            .contains("\"content_html\": \"<p>a]]>b</p>\""));
+    }
+
+    #[test]
+    fn entries_without_content_or_summary_stay_valid() {
+        let feed = Feed::new("Blog", "https://acme.dev/blog")
+            .entry(Entry::new("Hello", "https://acme.dev/blog/hello"));
+
+        let rss = feed.to_rss();
+        assert!(!rss.contains("<description></description>"));
+        assert!(!rss.contains("<content:encoded>"));
+
+        let atom = feed.to_atom();
+        assert!(!atom.contains("<summary>"));
+        assert!(!atom.contains("<content"));
+
+        let json = feed.to_json().unwrap();
+        assert!(json.contains("\"content_text\": \"\""));
+        assert!(!json.contains("\"summary\""));
+    }
+
+    #[test]
+    fn atom_falls_back_to_the_feed_title_and_date() {
+        let feed = Feed::new("Blog", "https://acme.dev/blog")
+            .entry(
+                Entry::new("Hello", "https://acme.dev/blog/hello")
+                    .published("2024-05-01T10:00:00Z"),
+            )
+            .entry(Entry::new("Draft", "https://acme.dev/blog/draft"));
+
+        let atom = feed.to_atom();
+        assert!(atom.contains("<author>\n<name>Blog</name>\n</author>"));
+        assert_eq!(
+            atom.matches("<updated>2024-05-01T10:00:00Z</updated>")
+                .count(),
+            3
+        );
+
+        let feed = Feed::new("Blog", "https://acme.dev/blog")
+            .entry(Entry::new("Hello", "https://acme.dev/blog/hello").author(Author::new("Ada")));
+        let atom = feed.to_atom();
+        assert_eq!(atom.matches("<author>").count(), 1);
+        assert!(atom.contains("<name>Ada</name>"));
+    }
+}
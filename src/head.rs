@@ -1,13 +1,131 @@
+use crate::attributes::AttributeOwners;
+use crate::csp::use_nonce;
+use crate::metadata::{use_metadata, Metadata as Meta};
 use crate::prelude::*;
+use crate::testing::HeadEntry;
+use crate::use_context;
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
-use web_sys::window;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use web_sys::wasm_bindgen::closure::Closure;
+use web_sys::wasm_bindgen::JsCast;
+use web_sys::{window, Element, HtmlHeadElement};
 use yew::virtual_dom::VTag;
 
-// Define METATYPES as a static array
-static METATYPES: [&'static str; 5] = ["name", "httpEquiv", "charSet", "itemProp", "property"];
+// Meta attributes identifying a meta tag, with the category they are deduplicated under.
+static METATYPES: [(&str, &str); 5] = [
+    ("name", "name"),
+    ("property", "property"),
+    ("http-equiv", "httpEquiv"),
+    ("httpEquiv", "httpEquiv"),
+    ("itemprop", "itemProp"),
+];
 
-// MetaCategories type to store meta category information
-type MetaCategories = HashMap<&'static str, HashSet<String>>;
+// Structured meta properties that may repeat, such as the `og:image` of each image. A `Head`
+// setting one of them replaces all of those set by the `Head` components mounted before it.
+static REPEATABLE_PROPERTIES: [&str; 10] = [
+    "og:image",
+    "og:video",
+    "og:audio",
+    "og:locale:alternate",
+    "article:author",
+    "article:tag",
+    "video:actor",
+    "video:director",
+    "video:tag",
+    "book:tag",
+];
+
+// Source of the ids ordering `Head` components by mount.
+static NEXT_HEAD_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns a new owner id. Components mounted later get greater ids, so their contributions win.
+fn next_owner_id() -> usize {
+    NEXT_HEAD_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Identifies a component contributing head elements.
+///
+/// Owners are ordered by their depth in the component tree, then by mount, so the contributions
+/// of a page win over those of its layouts, whatever the order they were mounted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Owner {
+    /// The number of ancestors of the component.
+    pub depth: usize,
+    /// The mount order of the component.
+    pub id: usize,
+}
+
+impl Owner {
+    /// Creates the owner of a component with the given number of ancestors.
+    pub(crate) fn new(depth: usize) -> Self {
+        Self {
+            depth,
+            id: next_owner_id(),
+        }
+    }
+
+    /// Creates the owner of the component of `scope`, at its depth in the component tree.
+    pub(crate) fn of<COMP: BaseComponent>(scope: &yew::html::Scope<COMP>) -> Self {
+        let mut depth = 0;
+        let mut parent = scope.get_parent();
+        while let Some(scope) = parent {
+            depth += 1;
+            parent = scope.get_parent();
+        }
+        Self::new(depth)
+    }
+}
+
+/// The values registered by mounted components, such as their head elements or the attributes
+/// they set on `<body>`, ordered by [`Owner`] so that the last value wins.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct OwnerStack<T> {
+    entries: Vec<(Owner, T)>,
+}
+
+impl<T> Default for OwnerStack<T> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<T> From<Vec<(Owner, T)>> for OwnerStack<T> {
+    fn from(mut entries: Vec<(Owner, T)>) -> Self {
+        entries.sort_by_key(|(owner, _)| *owner);
+        Self { entries }
+    }
+}
+
+impl<T> OwnerStack<T> {
+    /// Sets the value of `owner`, replacing its previous one.
+    pub(crate) fn register(&mut self, owner: Owner, value: T) {
+        let entries = &mut self.entries;
+        match entries.iter().position(|(other, _)| *other >= owner) {
+            Some(position) if entries[position].0 == owner => entries[position].1 = value,
+            Some(position) => entries.insert(position, (owner, value)),
+            None => entries.push((owner, value)),
+        }
+    }
+
+    /// Removes the value of the owner with the given id.
+    pub(crate) fn unregister(&mut self, id: usize) {
+        self.entries.retain(|(owner, _)| owner.id != id);
+    }
+
+    /// Returns the number of owners.
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the values, from the one losing to the one winning.
+    pub(crate) fn values(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.entries.iter().map(|(_, value)| value)
+    }
+}
 
 /// Generates the default `<head>` element with a charset meta tag.
 ///
@@ -28,7 +146,86 @@ pub fn default_head() -> Html {
     rsx! { <meta charset="utf-8" /> }
 }
 
-/// Reduces a vector of HTML components by flattening and filtering out duplicates.
+// Elements allowed inside `<head>`, kept as-is by `map_components`.
+static HEAD_TAGS: [&str; 8] = [
+    "title", "meta", "link", "script", "style", "base", "noscript", "template",
+];
+
+// Class added to every tag managed by next-rs.
+pub(crate) static TAG_CLASS: &str = "next-rs-tag";
+
+/// Collects the head elements of `component`, expanding fragments and wrapper tags.
+fn flatten_head(component: Html, head: &mut Vec<Html>) {
+    match component {
+        Html::VList(list) => {
+            for child in list.iter() {
+                flatten_head(child.clone(), head);
+            }
+        }
+        Html::VTag(tag) if HEAD_TAGS.contains(&tag.tag()) => head.push(Html::VTag(tag)),
+        Html::VTag(tag) => {
+            // Wrappers such as `<head>` or `<div>` only contribute their children.
+            if let Some(children) = tag.children() {
+                flatten_head(children.clone(), head);
+            }
+        }
+        Html::VText(text) => {
+            // Bare text is treated as the page title.
+            let mut tag = VTag::new("title");
+            tag.add_child(text.into());
+            head.push(tag.into());
+        }
+        // Components are rendered into `<head>` by `Head` itself, see `head_components`.
+        _ => {}
+    }
+}
+
+/// Collects the components among the children of a `Head`, expanding fragments and wrapper tags
+/// like [`flatten_head`].
+fn head_components(component: &Html, components: &mut Vec<Html>) {
+    match component {
+        Html::VList(list) => {
+            for child in list.iter() {
+                head_components(child, components);
+            }
+        }
+        Html::VTag(tag) if !HEAD_TAGS.contains(&tag.tag()) => {
+            if let Some(children) = tag.children() {
+                head_components(children, components);
+            }
+        }
+        Html::VComp(_) => components.push(component.clone()),
+        _ => {}
+    }
+}
+
+/// Adds the `next-rs-tag` marker class to a head element, keeping its existing classes.
+fn mark_tag(component: Html) -> Html {
+    match component {
+        Html::VTag(mut tag) => {
+            let class = tag
+                .attributes
+                .iter()
+                .find(|(key, _)| *key == "class")
+                .map(|(_, value)| value.to_string())
+                .unwrap_or_default();
+            if !class.split_whitespace().any(|name| name == TAG_CLASS) {
+                let class_name = format!("{} {}", TAG_CLASS, class);
+                tag.add_attribute("class", class_name.trim().to_string());
+            }
+            Html::VTag(tag)
+        }
+        _ => component,
+    }
+}
+
+/// Maps the children of a `Head` component to the elements rendered into `<head>`.
+///
+/// Every legal head element (`title`, `meta`, `link`, `script`, `style`, `base`, `noscript` and
+/// `template`) is kept with its attributes, fragments and wrapper tags are expanded, and bare text
+/// becomes a `<title>`. Components are left out: `Head` renders them into `<head>` as is.
+/// A default charset is prepended, duplicates are filtered with [`unique`], stylesheets rendered by
+/// `Stylesheet` are moved last by precedence, and every tag gets the `next-rs-tag` marker class.
 ///
 /// # Example
 /// ```rust
@@ -38,115 +235,623 @@ pub fn default_head() -> Html {
 /// let new_components = map_components(components);
 /// ```
 pub fn map_components(components: Vec<Html>) -> Vec<Html> {
-    let flattened: Vec<Html> = components
+    let mut flattened = vec![default_head()];
+    for component in components {
+        flatten_head(component, &mut flattened);
+    }
+
+    order_stylesheets(unique(flattened))
         .into_iter()
-        .flat_map(|c| match c {
-            Html::VTag(tag) => tag.children().into_iter().cloned().collect::<Vec<_>>(),
-            _ => vec![],
-        })
-        .collect();
+        .map(mark_tag)
+        .collect()
+}
+
+/// Returns the precedence of a stylesheet rendered by `Stylesheet`.
+fn precedence(head: &Html) -> Option<i32> {
+    match head {
+        Html::VTag(tag) => tag
+            .attributes
+            .iter()
+            .find(|(key, _)| *key == "data-precedence")
+            .map(|(_, value)| value.parse().unwrap_or_default()),
+        _ => None,
+    }
+}
 
-    let filtered: Vec<Html> = flattened.into_iter().filter(unique).collect();
+/// Moves the stylesheets rendered by `Stylesheet` after the other head elements, ordered by
+/// precedence, then by mount, so the cascade doesn't depend on navigation history.
+fn order_stylesheets(head: Vec<Html>) -> Vec<Html> {
+    let (mut stylesheets, mut head): (Vec<Html>, Vec<Html>) = head
+        .into_iter()
+        .partition(|html| precedence(html).is_some());
+    stylesheets.sort_by_key(|html| precedence(html).unwrap_or_default());
+    head.extend(stylesheets);
+    head
+}
 
-    let mut head = vec![default_head()];
+/// Keys the resolved head elements by [`head_key`], or else by their content, so that reordering
+/// them, such as when a stylesheet of lower precedence mounts, moves the existing nodes instead of
+/// recreating them, which would fetch stylesheets again.
+fn key_tags(head: Vec<Html>) -> Vec<Html> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    head.into_iter()
+        .enumerate()
+        .map(|(index, html)| {
+            let key = head_key(&html)
+                .or_else(|| HeadEntry::from_html(&html).map(|entry| entry.to_string()))
+                .unwrap_or_else(|| index.to_string());
+            // Repeatable properties may contain identical elements, which still need distinct keys.
+            let occurrence = seen.entry(key.clone()).or_default();
+            let key = format!("{}#{}", key, occurrence);
+            *occurrence += 1;
 
-    for child in filtered.clone() {
-        match child {
-            Html::VTag(_tag) => {
-                // TODO
-            }
-            Html::VText(text) => {
-                // Hack: Handle VText case, like title tag
-                let text_str = text.text;
-                let mut tag = VTag::new("title");
-                tag.add_child(text_str.into());
-                head.push(tag.into());
+            match html {
+                Html::VTag(mut tag) => {
+                    tag.key = Some(key.into());
+                    Html::VTag(tag)
+                }
+                html => html,
             }
-            Html::VComp(_component) => {
-                // TODO
+        })
+        .collect()
+}
+
+/// Returns the key under which a head element is deduplicated, if it shouldn't be duplicated.
+///
+/// Keys are `title`, `base`, `charset`, `canonical`, `manifest` and:
+/// - for other meta tags, the category and value of their `name`, `property`, `http-equiv` or
+///   `itemprop` attribute, such as `name:description`;
+/// - for alternate links, their `hreflang`, such as `alternate:fr`, or else their `type` and
+///   `href`, such as `alternate:application/rss+xml:https://acme.dev/feed.xml`;
+/// - for icons, their `rel`, `sizes` and `type`, such as `icon:32x32:image/png`;
+/// - for stylesheets, their `href`, such as `stylesheet:/blog.css`, and for inline styles, their
+///   `data-style-id`, such as `style:blog`;
+/// - for resource hints, their `rel` and `href`, such as `preconnect:https://fonts.gstatic.com`;
+/// - for JSON-LD scripts, the `@id` of their data, such as `json-ld:https://acme.dev/#org`.
+///
+/// # Example
+/// ```rust
+/// use next_rs::prelude::*;
+/// use next_rs::head::head_key;
+///
+/// let description = rsx! { <meta name="description" content="Next RS" /> };
+/// assert_eq!(head_key(&description), Some("name:description".to_string()));
+/// ```
+pub fn head_key(head: &Html) -> Option<String> {
+    let tag = match head {
+        Html::VTag(tag) => tag,
+        _ => return None,
+    };
+    let attribute = |name: &str| {
+        tag.attributes
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    match tag.tag() {
+        "title" | "base" => Some(tag.tag().to_string()),
+        "meta" if attribute("charset").is_some() => Some("charset".to_string()),
+        "meta" if repeatable_group(head).is_some() => None,
+        "meta" => METATYPES.iter().find_map(|(metatype, category)| {
+            attribute(metatype).map(|value| format!("{}:{}", category, value.to_lowercase()))
+        }),
+        "link" => {
+            let rel = attribute("rel")?.to_lowercase();
+            match rel.as_str() {
+                "canonical" | "manifest" => Some(rel),
+                "alternate" => attribute("hreflang")
+                    .map(|hreflang| format!("alternate:{}", hreflang.to_lowercase()))
+                    .or_else(|| {
+                        let href = attribute("href")?;
+                        attribute("type").map(|kind| format!("alternate:{}:{}", kind, href))
+                    }),
+                "stylesheet" => attribute("href").map(|href| format!("stylesheet:{}", href)),
+                "preload" | "modulepreload" | "preconnect" | "dns-prefetch" => {
+                    attribute("href").map(|href| format!("{}:{}", rel, href))
+                }
+                _ if rel.split_whitespace().any(|rel| rel.ends_with("icon")) => Some(format!(
+                    "{}:{}:{}",
+                    rel,
+                    attribute("sizes").unwrap_or_default(),
+                    attribute("type").unwrap_or_default()
+                )),
+                _ => None,
             }
-            _ => {}
         }
+        "script"
+            if attribute("type")
+                .is_some_and(|kind| kind.eq_ignore_ascii_case("application/ld+json")) =>
+        {
+            attribute("data-ld-id").map(|id| format!("json-ld:{}", id))
+        }
+        "style" => attribute("data-style-id").map(|id| format!("style:{}", id)),
+        _ => None,
     }
+}
 
-    // add next-rs trademark, rn
-    let final_result: Vec<Html> = head
-        .into_iter()
-        .map(|c| match c {
-            Html::VTag(mut tag) => {
-                let class_name = format!(
-                    "{} {}",
-                    "next-rs-tag",
-                    tag.attributes
-                        .iter()
-                        .find(|(key, _)| *key == "class")
-                        .map(|(_, value)| value)
-                        .unwrap_or_default()
-                );
-                tag.add_attribute("class", class_name);
-                Html::VTag(tag)
+/// Returns the group of a repeatable structured property, such as `og:image` for
+/// `og:image:width`.
+fn repeatable_group(head: &Html) -> Option<&'static str> {
+    let tag = match head {
+        Html::VTag(tag) if tag.tag() == "meta" => tag,
+        _ => return None,
+    };
+    let property = tag
+        .attributes
+        .iter()
+        .find(|(key, _)| *key == "property")
+        .map(|(_, value)| value.to_lowercase())?;
+
+    REPEATABLE_PROPERTIES
+        .iter()
+        .copied()
+        .find(|group| property == *group || property.starts_with(&format!("{}:", group)))
+}
+
+/// Removes head elements which shouldn't be duplicated, like <title/>.
+///
+/// When several elements share a [`head_key`], the last one wins and takes the place of the
+/// first one, so the output order is stable. Elements without a key are only removed when they
+/// are exact duplicates, except for repeatable structured properties: two images of the same size
+/// both need their own `og:image:width` and `og:image:height`.
+pub fn unique(head: Vec<Html>) -> Vec<Html> {
+    let mut result: Vec<Html> = Vec::with_capacity(head.len());
+    let mut positions: HashMap<String, usize> = HashMap::new();
+
+    for component in head {
+        match head_key(&component) {
+            Some(key) => match positions.get(&key) {
+                Some(&position) => result[position] = component,
+                None => {
+                    positions.insert(key, result.len());
+                    result.push(component);
+                }
+            },
+            None => {
+                if repeatable_group(&component).is_some() || !result.contains(&component) {
+                    result.push(component);
+                }
             }
-            _ => c,
-        })
-        .collect();
+        }
+    }
 
-    final_result
+    result
 }
 
-/// Returns a function for filtering head child elements which shouldn't be duplicated, like <title/>.
-pub fn unique(head: &Html) -> bool {
-    match head {
-        Html::VTag(tag) => match tag.tag() {
-            "title" | "base" => tag.key.is_some(),
-            "meta" => {
-                for metatype in METATYPES.iter() {
-                    if !tag
-                        .attributes
-                        .iter()
-                        .find(|(key, _)| *key == *metatype)
-                        .map(|(_, value)| value)
-                        .unwrap_or_default()
-                        .is_empty()
-                    {
-                        match *metatype {
-                            "charSet" => {
-                                if !tag
-                                    .attributes
-                                    .iter()
-                                    .find(|(key, _)| *key == "charSet")
-                                    .map(|(_, value)| value)
-                                    .unwrap_or_default()
-                                    .is_empty()
-                                {
-                                    return false;
-                                }
-                            }
-                            _ => {
-                                let category = tag
-                                    .attributes
-                                    .iter()
-                                    .find(|(key, _)| *key == *metatype)
-                                    .map(|(_, value)| value)
-                                    .unwrap_or_default();
-                                let mut meta_categories = MetaCategories::new();
-                                let categories = meta_categories
-                                    .entry(metatype)
-                                    .or_insert_with(|| HashSet::new());
-                                if categories.contains(&category.to_string()) {
-                                    return false;
-                                }
-
-                                categories.insert(category.to_string());
-                            }
-                        }
+/// The `Head` contributions of every mounted `Head` component, ordered by [`Owner`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HeadState {
+    entries: OwnerStack<Html>,
+}
+
+/// Actions updating the [`HeadState`].
+pub enum HeadAction {
+    /// Sets the children of the `Head` with the given owner.
+    Register(Owner, Html),
+    /// Removes the children of the `Head` with the given owner id.
+    Unregister(usize),
+}
+
+impl HeadState {
+    /// Resolves the elements to render into `<head>`, letting the deepest `Head` win, then the
+    /// last mounted one.
+    ///
+    /// Repeatable structured properties, such as the images of `og:image`, are taken as a whole
+    /// from the last `Head` setting them.
+    pub fn resolve(&self) -> Vec<Html> {
+        let mut groups: HashSet<&'static str> = HashSet::new();
+        let mut entries: Vec<Vec<Html>> = Vec::with_capacity(self.entries.len());
+
+        for html in self.entries.values().rev() {
+            let mut entry = Vec::new();
+            flatten_head(html.clone(), &mut entry);
+            let entry_groups: HashSet<&'static str> =
+                entry.iter().filter_map(repeatable_group).collect();
+            entry.retain(|head| !matches!(repeatable_group(head), Some(g) if groups.contains(g)));
+            groups.extend(entry_groups);
+            entries.push(entry);
+        }
+
+        map_components(entries.into_iter().rev().flatten().collect())
+    }
+
+    /// Applies an action to the state in place.
+    fn apply(&mut self, action: HeadAction) {
+        match action {
+            HeadAction::Register(owner, html) => self.entries.register(owner, html),
+            HeadAction::Unregister(id) => self.entries.unregister(id),
+        }
+    }
+}
+
+impl Reducible for HeadState {
+    type Action = HeadAction;
+
+    /// Reduces the state by applying the provided action.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action to apply to the state.
+    ///
+    /// # Returns
+    ///
+    /// (Rc<Self>): A new reference-counted state after applying the action.
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut state = (*self).clone();
+        state.apply(action);
+        state.into()
+    }
+}
+
+/// Represents the context of the head manager.
+#[derive(Clone, PartialEq)]
+pub struct HeadContext {
+    dispatcher: UseReducerDispatcher<HeadState>,
+    pub(crate) collector: Option<HeadCollector>,
+}
+
+// Attribute marking the head elements rendered by the server.
+pub(crate) static SSR_ATTRIBUTE: &str = "data-next-rs-ssr";
+
+// Text of the comments around the output of the components inside `Head` rendered by the server.
+static SSR_COMPONENTS: &str = "next-rs-ssr-components";
+
+/// Collects the head elements of a tree rendered on the server.
+///
+/// Effects don't run while rendering on the server, so `Head` components below a [`HeadProvider`]
+/// with a collector register their children while rendering instead. Once the tree is rendered,
+/// the collected elements can be rendered to a string and injected into the `<head>` of the HTML
+/// template. On the client, the head manager adopts the elements identical to the ones it
+/// resolves instead of rendering them again, so stylesheets aren't fetched twice, and removes the
+/// others once the first frame is rendered.
+///
+/// # Example
+/// ```rust,ignore
+/// use next_rs::prelude::*;
+/// use next_rs::head::{HeadCollector, HeadProvider};
+///
+/// #[derive(Properties, PartialEq)]
+/// pub struct AppProps {
+///     pub collector: Option<HeadCollector>,
+/// }
+///
+/// #[func]
+/// pub fn App(props: &AppProps) -> Html {
+///     rsx! {
+///         <HeadProvider collector={props.collector.clone()}>
+///             <Page />
+///         </HeadProvider>
+///     }
+/// }
+///
+/// async fn render_page(template: &str) -> String {
+///     let collector = HeadCollector::new();
+///     let props = AppProps { collector: Some(collector.clone()) };
+///     let body = yew::LocalServerRenderer::<App>::with_props(props).render().await;
+///     let head = collector.render().await;
+///
+///     template
+///         .replace("<!-- head -->", &head)
+///         .replace("<!-- body -->", &body)
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HeadCollector {
+    state: Rc<RefCell<HeadState>>,
+    components: Rc<RefCell<OwnerStack<Html>>>,
+    attributes: Rc<RefCell<HashMap<&'static str, AttributeOwners>>>,
+}
+
+impl PartialEq for HeadCollector {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.state, &other.state)
+    }
+}
+
+impl HeadCollector {
+    /// Creates an empty collector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the children of the `Head` with the given owner.
+    fn register(&self, owner: Owner, html: Html) {
+        self.state
+            .borrow_mut()
+            .apply(HeadAction::Register(owner, html));
+    }
+
+    /// Sets the components rendered into `<head>` by the `Head` with the given owner.
+    fn register_components(&self, owner: Owner, components: Html) {
+        self.components.borrow_mut().register(owner, components);
+    }
+
+    /// Sets the attributes `owner` sets on `<html>` or `<body>`.
+    pub(crate) fn register_attributes(
+        &self,
+        target: &'static str,
+        owner: Owner,
+        attributes: Vec<(String, String)>,
+    ) {
+        self.attributes
+            .borrow_mut()
+            .entry(target)
+            .or_default()
+            .register(owner, attributes);
+    }
+
+    /// Returns the attributes collected for `<html>` or `<body>`, resolved like on the client.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The element, `html` or `body`.
+    pub fn attributes(&self, target: &str) -> Vec<(String, String)> {
+        self.attributes
+            .borrow()
+            .get(target)
+            .map(|owners| {
+                owners
+                    .resolve()
+                    .into_iter()
+                    .filter_map(|(name, value)| value.map(|value| (name, value)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the collected head elements, resolved like on the client and marked as rendered
+    /// by the server. The components inside `Head` components are only rendered by
+    /// [`HeadCollector::render`].
+    pub fn tags(&self) -> Vec<Html> {
+        self.state
+            .borrow()
+            .resolve()
+            .into_iter()
+            .map(|component| match component {
+                Html::VTag(mut tag) => {
+                    tag.add_attribute(SSR_ATTRIBUTE, "");
+                    Html::VTag(tag)
+                }
+                component => component,
+            })
+            .collect()
+    }
+
+    /// Renders the collected head elements to an HTML string, followed by the output of the
+    /// components inside `Head` components, which the client replaces once it renders them.
+    #[cfg(feature = "ssr")]
+    pub async fn render(&self) -> String {
+        let render = |tags: Vec<Html>| {
+            yew::LocalServerRenderer::<HeadTags>::with_props(HeadTagsProps { tags })
+                .hydratable(false)
+                .render()
+        };
+        let tags = render(self.tags()).await;
+        let components: Vec<Html> = self.components.borrow().values().cloned().collect();
+        if components.is_empty() {
+            return tags;
+        }
+
+        format!(
+            "{}<!--{}-->{}<!--/{}-->",
+            tags,
+            SSR_COMPONENTS,
+            render(components).await,
+            SSR_COMPONENTS
+        )
+    }
+}
+
+/// Props for [`HeadTags`].
+#[cfg(feature = "ssr")]
+#[derive(Properties, Clone, PartialEq)]
+struct HeadTagsProps {
+    tags: Vec<Html>,
+}
+
+/// Renders collected head elements on the server.
+#[cfg(feature = "ssr")]
+#[func]
+fn HeadTags(props: &HeadTagsProps) -> Html {
+    rsx! { <>{ for props.tags.iter().cloned() }</> }
+}
+
+/// Props for [`HeadProvider`].
+#[derive(Properties, Clone, PartialEq)]
+pub struct HeadProviderProps {
+    /// Children components to be rendered.
+    #[prop_or_default]
+    pub children: Html,
+
+    /// Collects the head elements while rendering on the server.
+    #[prop_or_default]
+    pub collector: Option<HeadCollector>,
+}
+
+/// The head manager collecting the children of every `Head` component.
+///
+/// All `Head` components below it render into a single portal. Elements that shouldn't be
+/// duplicated, like `<title>` or `<meta name="description">`, are resolved per [`head_key`]: the
+/// most deeply nested `Head` wins, and among `Head` components at the same depth, the last mounted
+/// one. Elements are removed when the `Head` that owns them unmounts.
+///
+/// The router already renders a `HeadProvider`, so it is only needed in apps without one. A
+/// provider nested in another one only renders its children, unless it has a collector and the
+/// other one doesn't, such as the provider of a [`MetadataProvider`] rendered above it: it then
+/// collects the elements below it, along with the metadata defaults.
+#[func]
+pub fn HeadProvider(props: &HeadProviderProps) -> Html {
+    let parent = use_context::<HeadContext>();
+    let takes_over = parent
+        .as_ref()
+        .is_some_and(|parent| parent.collector.is_none() && props.collector.is_some());
+    let state = use_reducer(HeadState::default);
+    let server = use_mut_ref(|| match parent {
+        Some(_) => Vec::new(),
+        None => server_tags(),
+    });
+    let server_components = use_mut_ref(|| match parent {
+        Some(_) => Vec::new(),
+        None => server_components(),
+    });
+    let mounted = use_state(|| false);
+    let settled = use_state(|| false);
+
+    // Portals can't be hydrated, so the head elements are only rendered once mounted. Every
+    // `Head` of the first render has registered by the next frame, so the elements rendered by
+    // the server that the client didn't adopt by then are stale.
+    {
+        let mounted = mounted.setter();
+        let settled = settled.setter();
+        use_effect_with((), move |_| {
+            mounted.set(true);
+            if let Some(win) = window() {
+                let on_frame = Closure::once_into_js(move || settled.set(true));
+                let _ = win.request_animation_frame(on_frame.unchecked_ref());
+            }
+        });
+    }
+
+    let (adopted, rendered) = adopt(&server.borrow(), state.resolve());
+    {
+        let server = server.clone();
+        let settled = *settled;
+        use_effect(move || {
+            if settled {
+                // The components inside `Head` components have rendered their own output by now.
+                for node in server_components.borrow_mut().drain(..) {
+                    if let Some(parent) = node.parent_node() {
+                        let _ = parent.remove_child(&node);
                     }
                 }
-                true
+
+                let mut adopted = adopted.into_iter();
+                server.borrow_mut().retain(|(_, element)| {
+                    let keep = adopted.next().unwrap_or_default();
+                    if !keep {
+                        element.remove();
+                    }
+                    keep
+                });
             }
-            _ => true,
-        },
-        _ => true,
+        });
+    }
+
+    // Nested providers defer to the outermost one, so there is a single portal.
+    if parent.is_some() && !takes_over {
+        return props.children.clone();
     }
+
+    let context = HeadContext {
+        dispatcher: state.dispatcher(),
+        collector: props.collector.clone(),
+    };
+    if takes_over {
+        return rsx! {
+            <ContextProvider<HeadContext> context={context}>
+                <DefaultHead />
+                {props.children.clone()}
+            </ContextProvider<HeadContext>>
+        };
+    }
+
+    let portal = match document_head() {
+        Some(head) if *mounted => {
+            create_portal(rsx! {<>{ for key_tags(rendered) }</> }, head.into())
+        }
+        _ => Html::default(),
+    };
+
+    rsx! {
+        <ContextProvider<HeadContext> context={context}>
+            {props.children.clone()}
+            {portal}
+        </ContextProvider<HeadContext>>
+    }
+}
+
+/// Returns the head elements rendered by the server, with their entries.
+fn server_tags() -> Vec<(HeadEntry, Element)> {
+    let rendered = match document_head().and_then(|head| {
+        head.query_selector_all(&format!("[{}]", SSR_ATTRIBUTE))
+            .ok()
+    }) {
+        Some(rendered) => rendered,
+        None => return Vec::new(),
+    };
+
+    (0..rendered.length())
+        .filter_map(|index| rendered.get(index))
+        .filter_map(|node| node.dyn_into::<Element>().ok())
+        .map(|element| (HeadEntry::from_element(&element), element))
+        .collect()
+}
+
+/// Returns the nodes rendered by the server for the components inside `Head` components,
+/// including the comments around them.
+fn server_components() -> Vec<web_sys::Node> {
+    let head = match document_head() {
+        Some(head) => head,
+        None => return Vec::new(),
+    };
+    let open = SSR_COMPONENTS.to_string();
+    let close = format!("/{}", SSR_COMPONENTS);
+
+    let mut nodes = Vec::new();
+    let mut inside = false;
+    let mut node = head.first_child();
+    while let Some(current) = node {
+        let marker = match current.node_type() == web_sys::Node::COMMENT_NODE {
+            true => current.node_value(),
+            false => None,
+        };
+        if marker.as_ref() == Some(&open) {
+            inside = true;
+        }
+        if inside {
+            nodes.push(current.clone());
+        }
+        if marker.as_ref() == Some(&close) {
+            inside = false;
+        }
+        node = current.next_sibling();
+    }
+    nodes
+}
+
+/// Matches the resolved head elements with the identical elements rendered by the server.
+///
+/// # Returns
+///
+/// (Vec<bool>, Vec<Html>): Whether each server element is adopted, and the resolved elements
+/// left for the portal to render.
+fn adopt(server: &[(HeadEntry, Element)], resolved: Vec<Html>) -> (Vec<bool>, Vec<Html>) {
+    let mut adopted = vec![false; server.len()];
+    let rendered = resolved
+        .into_iter()
+        .filter(|head| {
+            let entry = match HeadEntry::from_html(head) {
+                Some(entry) => entry,
+                None => return true,
+            };
+            let index = server
+                .iter()
+                .enumerate()
+                .find(|(index, (server, _))| !adopted[*index] && *server == entry)
+                .map(|(index, _)| index);
+            match index {
+                Some(index) => {
+                    adopted[index] = true;
+                    false
+                }
+                None => true,
+            }
+        })
+        .collect();
+
+    (adopted, rendered)
 }
 
 // Define the HeadProps struct
@@ -157,6 +862,12 @@ pub struct HeadProps {
 
 /// A component representing the `<head>` element.
 ///
+/// Inside a [`HeadProvider`], its children are merged with those of the other mounted `Head`
+/// components, and removed when it unmounts. Below a [`MetadataProvider`], its `<title>` is
+/// wrapped in the default title template. Components inside it are rendered into `<head>` as is,
+/// keeping the contexts of the `Head`, and collected on the server; their output isn't merged
+/// with the other elements, so a component may render its own `Head` instead.
+///
 /// # Example
 /// ```rust
 /// use next_rs::head::Head;
@@ -174,10 +885,1011 @@ pub struct HeadProps {
 /// ```
 #[func]
 pub fn Head(props: &HeadProps) -> Html {
-    let state: Vec<Html> = map_components(vec![props.children.clone()]);
+    let defaults = use_default_metadata();
+    let nonce = use_nonce();
+    let children = match &defaults.title_template {
+        Some(template) => apply_title_template(props.children.clone(), template),
+        None => props.children.clone(),
+    };
+    let children = match nonce {
+        Some(nonce) => apply_nonce(children, &nonce),
+        None => children,
+    };
+    let managed = use_context::<HeadContext>().is_some();
+    let mut components = Vec::new();
+    head_components(&children, &mut components);
 
-    let document = window().and_then(|win| win.document()).unwrap();
-    let head = document.head().expect("Failed to get head element");
+    rsx! {
+        <>
+            {register_head(managed, children)}
+            if !components.is_empty() {
+                <HeadComponents children={Html::from_iter(components)} />
+            }
+        </>
+    }
+}
+
+/// Props for [`HeadComponents`].
+#[derive(Properties, Clone, PartialEq)]
+struct HeadComponentsProps {
+    children: Html,
+}
+
+/// Renders the components inside a `Head` into `<head>`, or collects them on the server.
+#[func]
+fn HeadComponents(props: &HeadComponentsProps) -> Html {
+    let context = use_context::<HeadContext>();
+    let owner = *use_state(|| Owner::new(0));
+    let mounted = use_state(|| false);
+    {
+        let mounted = mounted.setter();
+        use_effect_with((), move |_| mounted.set(true));
+    }
 
-    create_portal(rsx! {<>{ for state.into_iter() }</> }, head.clone().into())
+    // Effects don't run on the server, so register while rendering.
+    if let Some(collector) = context
+        .as_ref()
+        .and_then(|context| context.collector.as_ref())
+    {
+        collector.register_components(owner, props.children.clone());
+    }
+
+    // Portals can't be hydrated, so the components are only rendered once mounted.
+    match document_head() {
+        Some(head) if *mounted => create_portal(props.children.clone(), head.into()),
+        _ => Html::default(),
+    }
+}
+
+/// Registers head elements with the head manager if there is one, or else renders them straight
+/// into `<head>`.
+fn register_head(managed: bool, children: Html) -> Html {
+    match managed {
+        true => rsx! { <HeadRegistration children={children} /> },
+        false => head_portal(children),
+    }
+}
+
+/// Props for [`HeadRegistration`].
+#[derive(Properties, Clone, PartialEq)]
+struct HeadRegistrationProps {
+    children: Html,
+}
+
+/// Registers the elements of a `Head` with the head manager, owned at the depth of the `Head` in
+/// the component tree.
+struct HeadRegistration {
+    owner: Owner,
+    context: Option<HeadContext>,
+}
+
+impl Component for HeadRegistration {
+    type Message = ();
+    type Properties = HeadRegistrationProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let owner = Owner::of(ctx.link());
+        let context = ctx
+            .link()
+            .context::<HeadContext>(Callback::noop())
+            .map(|(context, _)| context);
+
+        // Effects don't run on the server, so register while rendering.
+        if let Some(collector) = context
+            .as_ref()
+            .and_then(|context| context.collector.as_ref())
+        {
+            collector.register(owner, ctx.props().children.clone());
+        }
+
+        Self { owner, context }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props() != old_props {
+            self.register(ctx.props().children.clone());
+        }
+        false
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        Html::default()
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if first_render {
+            self.register(ctx.props().children.clone());
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        if let Some(context) = &self.context {
+            context
+                .dispatcher
+                .dispatch(HeadAction::Unregister(self.owner.id));
+        }
+    }
+}
+
+impl HeadRegistration {
+    /// Sets the elements of the `Head` in the head manager.
+    fn register(&self, children: Html) {
+        if let Some(context) = &self.context {
+            context
+                .dispatcher
+                .dispatch(HeadAction::Register(self.owner, children));
+        }
+    }
+}
+
+/// Returns the `<head>` element, or `None` outside of a browser, such as on the server.
+fn document_head() -> Option<HtmlHeadElement> {
+    if !cfg!(target_arch = "wasm32") {
+        return None;
+    }
+    window()
+        .and_then(|win| win.document())
+        .and_then(|doc| doc.head())
+}
+
+/// Renders head elements straight into `<head>`, for components used without a head manager.
+fn head_portal(children: Html) -> Html {
+    let state: Vec<Html> = map_components(vec![children]);
+
+    match document_head() {
+        Some(head) => create_portal(rsx! {<>{ for key_tags(state) }</> }, head.into()),
+        None => Html::default(),
+    }
+}
+
+/// A hook registering head elements with the head manager.
+///
+/// The elements are merged with those of the other `Head` components, and removed when the
+/// calling component unmounts. Hooks can't see their place in the component tree, so the elements
+/// are registered by the returned node, which the calling component must render: like a `Head`
+/// rendered by that component, a deeper `Head` wins over it.
+///
+/// # Arguments
+///
+/// * `children`: The head elements to register.
+///
+/// # Returns
+///
+/// (Html): The node registering the elements, or rendering them straight into `<head>` without a
+/// [`HeadProvider`].
+///
+/// # Example
+/// ```rust
+/// use next_rs::head::use_head;
+/// use next_rs::prelude::*;
+///
+/// #[func]
+/// pub fn MyComponent() -> Html {
+///     let head = use_head(rsx! { <title>{"Next RS Title"}</title> });
+///
+///     rsx! {
+///         <>
+///             {head}
+///             <h1>{"Next RS"}</h1>
+///         </>
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_head(children: Html) -> Html {
+    let managed = use_context::<HeadContext>().is_some();
+
+    register_head(managed, children)
+}
+
+/// A component rendering typed [`crate::metadata::Metadata`] into `<head>`.
+///
+/// The fields of the metadata are its props, so a whole struct can be passed with `..meta`. They
+/// are merged into the site defaults of the nearest [`MetadataProvider`] per [`merge_metadata`].
+///
+/// # Example
+/// ```rust
+/// use next_rs::prelude::*;
+/// use next_rs::head::Metadata;
+///
+/// #[func]
+/// pub fn MyComponent() -> Html {
+///
+///     rsx! {
+///         <Metadata
+///             title="Next RS Title"
+///             description="Next RS Description"
+///             viewport="width=device-width, initial-scale=1"
+///         />
+///     }
+/// }
+/// ```
+#[func]
+pub fn Metadata(props: &Meta) -> Html {
+    use_metadata(props.clone())
+}
+
+/// Merges the metadata of a page into the site defaults.
+///
+/// The rules are:
+/// - A title set by the page is wrapped in its own `title_template`, or else in the default one.
+///   Without a page title, the default title is rendered as is by the [`MetadataProvider`].
+/// - Other fields, including `keywords`, `authors`, `open_graph` and `twitter`, are taken from the
+///   page when set, and inherited from the defaults otherwise.
+/// - Page icons replace the default icons with the same `rel`, `sizes` and type, and page
+///   alternates replace the default alternates with the same `hreflang`. Other default icons and
+///   alternates are kept, before those of the page.
+///
+/// # Example
+/// ```rust
+/// use next_rs::head::merge_metadata;
+/// use next_rs::metadata::Metadata;
+///
+/// let defaults = Metadata {
+///     title_template: Some("%s | Acme".into()),
+///     description: Some("Tools for builders".into()),
+///     ..Metadata::default()
+/// };
+/// let page = Metadata {
+///     title: Some("Pricing".into()),
+///     ..Metadata::default()
+/// };
+///
+/// let merged = merge_metadata(&defaults, &page);
+/// assert_eq!(merged.full_title().as_deref(), Some("Pricing | Acme"));
+/// assert_eq!(merged.description.as_deref(), Some("Tools for builders"));
+/// ```
+pub fn merge_metadata(defaults: &Meta, page: &Meta) -> Meta {
+    Meta {
+        title: page.title.clone(),
+        title_template: pick(&page.title_template, &defaults.title_template),
+        description: pick(&page.description, &defaults.description),
+        keywords: pick_list(&page.keywords, &defaults.keywords),
+        authors: pick_list(&page.authors, &defaults.authors),
+        origin: pick(&page.origin, &defaults.origin),
+        basename: pick(&page.basename, &defaults.basename),
+        canonical: pick(&page.canonical, &defaults.canonical),
+        robots: pick(&page.robots, &defaults.robots),
+        viewport: pick(&page.viewport, &defaults.viewport),
+        theme_color: pick(&page.theme_color, &defaults.theme_color),
+        open_graph: pick(&page.open_graph, &defaults.open_graph),
+        twitter: pick(&page.twitter, &defaults.twitter),
+        icons: replace_by(&page.icons, &defaults.icons, |icon| {
+            (
+                icon.rel.clone(),
+                icon.sizes.clone(),
+                icon.media_type.clone(),
+            )
+        }),
+        manifest: pick(&page.manifest, &defaults.manifest),
+        alternates: replace_by(&page.alternates, &defaults.alternates, |alternate| {
+            alternate
+                .hreflang
+                .clone()
+                .ok_or_else(|| alternate.href.clone())
+        }),
+    }
+}
+
+/// Returns the page value if set, or else the default one.
+fn pick<T: Clone>(page: &Option<T>, default: &Option<T>) -> Option<T> {
+    page.clone().or_else(|| default.clone())
+}
+
+/// Returns the page list if not empty, or else the default one.
+fn pick_list<T: Clone>(page: &[T], default: &[T]) -> Vec<T> {
+    match page.is_empty() {
+        true => default.to_vec(),
+        false => page.to_vec(),
+    }
+}
+
+/// Returns the default items not replaced by a page item with the same key, then the page items.
+fn replace_by<T: Clone, K: PartialEq>(page: &[T], default: &[T], key: impl Fn(&T) -> K) -> Vec<T> {
+    default
+        .iter()
+        .filter(|item| page.iter().all(|other| key(other) != key(item)))
+        .chain(page)
+        .cloned()
+        .collect()
+}
+
+/// Wraps the text of every `<title>` in `head` with `template`, where `%s` is the title.
+fn apply_title_template(head: Html, template: &str) -> Html {
+    let mut flattened = Vec::new();
+    flatten_head(head, &mut flattened);
+
+    flattened
+        .into_iter()
+        .map(|component| match &component {
+            Html::VTag(tag) if tag.tag() == "title" => {
+                let title = tag.children().map(text_content).unwrap_or_default();
+                let title = template.replace("%s", &title);
+                rsx! { <title>{title}</title> }
+            }
+            _ => component,
+        })
+        .collect()
+}
+
+/// Adds `nonce` to the inline `<script>` and `<style>` elements of `head` lacking one, so they
+/// satisfy the Content Security Policy.
+fn apply_nonce(head: Html, nonce: &AttrValue) -> Html {
+    let mut flattened = Vec::new();
+    flatten_head(head, &mut flattened);
+
+    flattened
+        .into_iter()
+        .map(|component| match component {
+            Html::VTag(mut tag)
+                if matches!(tag.tag(), "script" | "style")
+                    && !tag.attributes.iter().any(|(key, _)| key == "nonce") =>
+            {
+                tag.add_attribute("nonce", nonce.clone());
+                Html::VTag(tag)
+            }
+            _ => component,
+        })
+        .collect()
+}
+
+/// Returns the text content of an element.
+pub(crate) fn text_content(html: &Html) -> String {
+    match html {
+        Html::VText(text) => text.text.to_string(),
+        Html::VList(list) => list.iter().map(text_content).collect(),
+        Html::VTag(tag) => tag.children().map(text_content).unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+/// Represents the site-wide metadata defaults.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MetadataContext {
+    /// The default metadata of every page.
+    pub defaults: Rc<Meta>,
+}
+
+/// Props for [`MetadataProvider`].
+#[derive(Properties, Clone, PartialEq)]
+pub struct MetadataProviderProps {
+    /// The default metadata of every page, such as the site title and its `title_template`.
+    #[prop_or_default]
+    pub defaults: Meta,
+    /// Children components to be rendered.
+    #[prop_or_default]
+    pub children: Html,
+    /// Collects the head elements while rendering on the server, like the collector of a
+    /// [`HeadProvider`].
+    #[prop_or_default]
+    pub collector: Option<HeadCollector>,
+}
+
+/// Provides site-wide metadata defaults that pages merge into.
+///
+/// The defaults are rendered into `<head>` with the lowest priority, so pages only specify what
+/// differs. The default title is rendered as is, while page titles, whether set through
+/// [`Metadata`], [`crate::metadata::use_metadata`] or a `<title>` inside [`Head`], are wrapped in
+/// the `title_template`. See [`merge_metadata`] for the merge rules.
+///
+/// It renders a [`HeadProvider`] unless there is one above it. On the server, either set its
+/// `collector` or render a [`HeadProvider`] with a collector below or above it.
+///
+/// # Example
+/// ```rust,no_run
+/// use next_rs::prelude::*;
+/// use next_rs::head::MetadataProvider;
+/// use next_rs::metadata::Metadata;
+///
+/// #[func]
+/// pub fn App() -> Html {
+///     let defaults = Metadata {
+///         title: Some("Acme".into()),
+///         title_template: Some("%s | Acme".into()),
+///         description: Some("Tools for builders".into()),
+///         ..Metadata::default()
+///     };
+///
+///     rsx! {
+///         <MetadataProvider defaults={defaults}>
+///             <div />
+///         </MetadataProvider>
+///     }
+/// }
+/// ```
+#[func]
+pub fn MetadataProvider(props: &MetadataProviderProps) -> Html {
+    let context = MetadataContext {
+        defaults: Rc::new(props.defaults.clone()),
+    };
+
+    rsx! {
+        <HeadProvider collector={props.collector.clone()}>
+            <ContextProvider<MetadataContext> context={context}>
+                <DefaultHead />
+                {props.children.clone()}
+            </ContextProvider<MetadataContext>>
+        </HeadProvider>
+    }
+}
+
+/// Registers the metadata defaults of the nearest [`MetadataProvider`] with the head manager.
+/// The default title is rendered as is, not wrapped in the title template.
+#[func]
+fn DefaultHead() -> Html {
+    let context = use_context::<MetadataContext>();
+    let managed = use_context::<HeadContext>().is_some();
+
+    match context {
+        Some(context) => {
+            let defaults = Meta {
+                title_template: None,
+                ..(*context.defaults).clone()
+            };
+            register_head(managed, defaults.to_html())
+        }
+        None => Html::default(),
+    }
+}
+
+/// A hook to access the site-wide metadata defaults, empty outside of a [`MetadataProvider`].
+#[hook]
+pub fn use_default_metadata() -> Rc<Meta> {
+    use_context::<MetadataContext>()
+        .map(|context| context.defaults)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs a future rendering on the server to completion.
+    #[cfg(feature = "ssr")]
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        tokio::task::LocalSet::new().block_on(&runtime, future)
+    }
+    use crate::metadata::{Alternate, Icon};
+
+    fn owner(depth: usize, id: usize) -> Owner {
+        Owner { depth, id }
+    }
+
+    fn defaults() -> Meta {
+        Meta {
+            title: Some("Acme".into()),
+            title_template: Some("%s | Acme".into()),
+            description: Some("Tools for builders".into()),
+            keywords: vec!["tools".into()],
+            robots: Some("index, follow".into()),
+            icons: vec![Icon::new("/favicon.ico"), Icon::new("/icon-32.png")],
+            alternates: vec![Alternate {
+                href: "/fr".into(),
+                hreflang: Some("fr".into()),
+                ..Alternate::default()
+            }],
+            ..Meta::default()
+        }
+    }
+
+    #[test]
+    fn page_title_uses_default_template() {
+        let page = Meta {
+            title: Some("Pricing".into()),
+            ..Meta::default()
+        };
+        let merged = merge_metadata(&defaults(), &page);
+        assert_eq!(merged.full_title().as_deref(), Some("Pricing | Acme"));
+    }
+
+    #[test]
+    fn page_template_overrides_default_template() {
+        let page = Meta {
+            title: Some("Docs".into()),
+            title_template: Some("%s - Acme Docs".into()),
+            ..Meta::default()
+        };
+        let merged = merge_metadata(&defaults(), &page);
+        assert_eq!(merged.full_title().as_deref(), Some("Docs - Acme Docs"));
+    }
+
+    #[test]
+    fn default_title_is_not_inherited() {
+        let merged = merge_metadata(&defaults(), &Meta::default());
+        assert_eq!(merged.full_title(), None);
+    }
+
+    #[test]
+    fn page_fields_override_defaults() {
+        let page = Meta {
+            description: Some("Plans for every team".into()),
+            keywords: vec!["pricing".into(), "plans".into()],
+            ..Meta::default()
+        };
+        let merged = merge_metadata(&defaults(), &page);
+        assert_eq!(merged.description.as_deref(), Some("Plans for every team"));
+        assert_eq!(merged.keywords, page.keywords);
+        assert_eq!(merged.robots.as_deref(), Some("index, follow"));
+    }
+
+    #[test]
+    fn page_icons_and_alternates_replace_matching_defaults() {
+        let page = Meta {
+            icons: vec![Icon {
+                sizes: Some("32x32".into()),
+                ..Icon::new("/page-32.png")
+            }],
+            alternates: vec![Alternate {
+                href: "/fr/pricing".into(),
+                hreflang: Some("fr".into()),
+                ..Alternate::default()
+            }],
+            ..Meta::default()
+        };
+        let merged = merge_metadata(&defaults(), &page);
+        let icons: Vec<&str> = merged.icons.iter().map(|icon| icon.href.as_str()).collect();
+        assert_eq!(icons, ["/favicon.ico", "/icon-32.png", "/page-32.png"]);
+        assert_eq!(merged.alternates, page.alternates);
+
+        let page = Meta {
+            icons: vec![Icon::new("/page.ico")],
+            ..Meta::default()
+        };
+        let merged = merge_metadata(&defaults(), &page);
+        assert_eq!(merged.icons, page.icons);
+    }
+
+    #[test]
+    fn title_template_wraps_head_titles() {
+        let head = rsx! {
+            <>
+                <title>{"Pricing"}</title>
+                <meta name="description" content="Plans" />
+            </>
+        };
+        let mut resolved = Vec::new();
+        flatten_head(apply_title_template(head, "%s | Acme"), &mut resolved);
+        let keys: Vec<Option<String>> = resolved.iter().map(head_key).collect();
+        assert_eq!(
+            keys,
+            [
+                Some("title".to_string()),
+                Some("name:description".to_string())
+            ]
+        );
+        assert_eq!(text_content(&resolved[0]), "Pricing | Acme");
+    }
+
+    #[test]
+    fn repeatable_properties_are_replaced_as_a_whole() {
+        let state = HeadState {
+            entries: vec![
+                (
+                    owner(0, 0),
+                    rsx! {
+                        <>
+                            <meta property="og:image" content="/default.png" />
+                            <meta property="og:site_name" content="Acme" />
+                        </>
+                    },
+                ),
+                (
+                    owner(0, 1),
+                    rsx! {
+                        <>
+                            <meta property="og:image" content="/a.png" />
+                            <meta property="og:image:width" content="1200" />
+                            <meta property="og:image" content="/b.png" />
+                        </>
+                    },
+                ),
+            ]
+            .into(),
+        };
+        let resolved = state.resolve();
+        let images = resolved
+            .iter()
+            .filter(|html| repeatable_group(html) == Some("og:image"))
+            .count();
+        assert_eq!(images, 3);
+        assert!(resolved
+            .iter()
+            .any(|html| head_key(html).as_deref() == Some("property:og:site_name")));
+    }
+
+    #[test]
+    fn repeatable_properties_keep_identical_sizes() {
+        let state = HeadState {
+            entries: vec![(
+                owner(0, 0),
+                rsx! {
+                    <>
+                        <meta property="og:image" content="/a.png" />
+                        <meta property="og:image:width" content="1200" />
+                        <meta property="og:image:height" content="630" />
+                        <meta property="og:image" content="/b.png" />
+                        <meta property="og:image:width" content="1200" />
+                        <meta property="og:image:height" content="630" />
+                    </>
+                },
+            )]
+            .into(),
+        };
+        let properties: Vec<String> = state
+            .resolve()
+            .iter()
+            .filter(|html| repeatable_group(html).is_some())
+            .filter_map(|html| match html {
+                Html::VTag(tag) => tag
+                    .attributes
+                    .iter()
+                    .find(|(key, _)| *key == "property")
+                    .map(|(_, value)| value.to_string()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            properties,
+            [
+                "og:image",
+                "og:image:width",
+                "og:image:height",
+                "og:image",
+                "og:image:width",
+                "og:image:height"
+            ]
+        );
+    }
+
+    #[test]
+    fn later_head_entries_win() {
+        let state = HeadState {
+            entries: vec![
+                (owner(0, 0), rsx! { <title>{"Acme"}</title> }),
+                (owner(0, 1), rsx! { <title>{"Pricing | Acme"}</title> }),
+            ]
+            .into(),
+        };
+        let resolved = state.resolve();
+        let titles: Vec<String> = resolved
+            .iter()
+            .filter(|html| head_key(html).as_deref() == Some("title"))
+            .map(text_content)
+            .collect();
+        assert_eq!(titles, ["Pricing | Acme"]);
+    }
+
+    #[test]
+    fn deeper_head_entries_win_over_later_ones() {
+        let mut state = HeadState::default();
+        state.apply(HeadAction::Register(
+            owner(3, 0),
+            rsx! { <title>{"Pricing | Acme"}</title> },
+        ));
+        // A layout `Head` mounted after the page, such as after it was re-rendered.
+        state.apply(HeadAction::Register(
+            owner(1, 1),
+            rsx! { <title>{"Acme"}</title> },
+        ));
+        let titles: Vec<String> = state
+            .resolve()
+            .iter()
+            .filter(|html| head_key(html).as_deref() == Some("title"))
+            .map(text_content)
+            .collect();
+        assert_eq!(titles, ["Pricing | Acme"]);
+
+        state.apply(HeadAction::Unregister(0));
+        let titles: Vec<String> = state
+            .resolve()
+            .iter()
+            .filter(|html| head_key(html).as_deref() == Some("title"))
+            .map(text_content)
+            .collect();
+        assert_eq!(titles, ["Acme"]);
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn page_head_wins_over_a_layout_hook() {
+        #[func]
+        fn Page() -> Html {
+            rsx! {
+                <Head>
+                    <title>{"Pricing"}</title>
+                </Head>
+            }
+        }
+
+        #[func]
+        fn Layout() -> Html {
+            let head = use_head(rsx! {
+                <>
+                    <title>{"Acme"}</title>
+                    <meta name="description" content="Tools for builders" />
+                </>
+            });
+
+            rsx! {
+                <>
+                    {head}
+                    <Page />
+                </>
+            }
+        }
+
+        let collector = HeadCollector::new();
+        block_on(
+            yew::LocalServerRenderer::<HeadProvider>::with_props(HeadProviderProps {
+                children: rsx! { <Layout /> },
+                collector: Some(collector.clone()),
+            })
+            .render(),
+        );
+        let mut collected: Vec<String> = collector
+            .tags()
+            .iter()
+            .filter_map(HeadEntry::from_html)
+            .map(|entry| entry.to_string())
+            .collect();
+        collected.sort();
+        assert_eq!(
+            collected,
+            [
+                "<meta charset=\"utf-8\">",
+                "<meta content=\"Tools for builders\" name=\"description\">",
+                "<title>Pricing</title>",
+            ]
+        );
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn metadata_defaults_are_collected_whatever_the_provider_order() {
+        #[func]
+        fn Page() -> Html {
+            rsx! {
+                <Head>
+                    <title>{"Pricing"}</title>
+                </Head>
+            }
+        }
+
+        let defaults = Meta {
+            title: Some("Acme".into()),
+            title_template: Some("%s | Acme".into()),
+            description: Some("Tools for builders".into()),
+            ..Meta::default()
+        };
+        let entries = |collector: &HeadCollector| -> Vec<String> {
+            collector
+                .tags()
+                .iter()
+                .filter_map(HeadEntry::from_html)
+                .map(|entry| entry.to_string())
+                .collect()
+        };
+        let expected = [
+            "<meta charset=\"utf-8\">",
+            "<meta content=\"Tools for builders\" name=\"description\">",
+            "<title>Pricing | Acme</title>",
+        ];
+
+        // The metadata defaults above the app, as rendered by its root component.
+        let collector = HeadCollector::new();
+        block_on(
+            yew::LocalServerRenderer::<MetadataProvider>::with_props(MetadataProviderProps {
+                defaults: defaults.clone(),
+                children: rsx! {
+                    <HeadProvider collector={Some(collector.clone())}>
+                        <Page />
+                    </HeadProvider>
+                },
+                collector: None,
+            })
+            .render(),
+        );
+        let mut collected = entries(&collector);
+        collected.sort();
+        assert_eq!(collected, expected);
+
+        // The metadata defaults below the provider, as rendered by `Document::render`.
+        let collector = HeadCollector::new();
+        block_on(
+            yew::LocalServerRenderer::<HeadProvider>::with_props(HeadProviderProps {
+                children: rsx! {
+                    <MetadataProvider defaults={defaults}>
+                        <Page />
+                    </MetadataProvider>
+                },
+                collector: Some(collector.clone()),
+            })
+            .render(),
+        );
+        let mut collected = entries(&collector);
+        collected.sort();
+        assert_eq!(collected, expected);
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn page_metadata_wins_over_layout_metadata() {
+        #[func]
+        fn Page() -> Html {
+            rsx! { <Metadata title="Pricing" /> }
+        }
+
+        #[func]
+        fn Layout() -> Html {
+            let metadata = use_metadata(Meta {
+                title: Some("Home".into()),
+                description: Some("Tools for builders".into()),
+                ..Meta::default()
+            });
+
+            rsx! {
+                <>
+                    {metadata}
+                    <Page />
+                </>
+            }
+        }
+
+        let collector = HeadCollector::new();
+        block_on(
+            yew::LocalServerRenderer::<MetadataProvider>::with_props(MetadataProviderProps {
+                defaults: Meta {
+                    title_template: Some("%s | Acme".into()),
+                    ..Meta::default()
+                },
+                children: rsx! { <Layout /> },
+                collector: Some(collector.clone()),
+            })
+            .render(),
+        );
+        let mut collected: Vec<String> = collector
+            .tags()
+            .iter()
+            .filter_map(HeadEntry::from_html)
+            .map(|entry| entry.to_string())
+            .collect();
+        collected.sort();
+        assert_eq!(
+            collected,
+            [
+                "<meta charset=\"utf-8\">",
+                "<meta content=\"Tools for builders\" name=\"description\">",
+                "<title>Pricing | Acme</title>",
+            ]
+        );
+    }
+
+    #[test]
+    fn components_inside_head_are_rendered_apart() {
+        #[func]
+        fn Tags() -> Html {
+            rsx! { <meta name="description" content="From a component" /> }
+        }
+
+        let head = rsx! {
+            <>
+                <title>{"Blog"}</title>
+                <div><Tags /></div>
+            </>
+        };
+        let keys: Vec<String> = map_components(vec![head.clone()])
+            .iter()
+            .map(|html| head_key(html).unwrap_or_default())
+            .collect();
+        assert_eq!(keys, ["charset", "title"]);
+
+        let mut components = Vec::new();
+        head_components(&head, &mut components);
+        assert_eq!(components, vec![rsx! { <Tags /> }]);
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn components_inside_head_are_collected_on_the_server() {
+        #[func]
+        fn Tags() -> Html {
+            rsx! { <meta name="description" content="From a component" /> }
+        }
+
+        #[func]
+        fn Page() -> Html {
+            rsx! {
+                <Head>
+                    <title>{"Blog"}</title>
+                    <Tags />
+                </Head>
+            }
+        }
+
+        let head = block_on(async {
+            let collector = HeadCollector::new();
+            yew::LocalServerRenderer::<HeadProvider>::with_props(HeadProviderProps {
+                children: rsx! { <Page /> },
+                collector: Some(collector.clone()),
+            })
+            .render()
+            .await;
+            collector.render().await
+        });
+        assert!(head.contains(">Blog</title>"), "{}", head);
+        assert!(head.ends_with(
+            "<!--next-rs-ssr-components-->\
+             <meta name=\"description\" content=\"From a component\">\
+             <!--/next-rs-ssr-components-->"
+        ));
+    }
+
+    #[test]
+    fn stylesheets_are_deduplicated_and_ordered_by_precedence() {
+        let state = HeadState {
+            entries: vec![
+                (
+                    owner(0, 0),
+                    rsx! {
+                        <>
+                            <link rel="stylesheet" href="/page.css" data-precedence="1" />
+                            <link rel="stylesheet" href="/base.css" data-precedence="0" />
+                        </>
+                    },
+                ),
+                (
+                    owner(0, 1),
+                    rsx! {
+                        <>
+                            <link rel="stylesheet" href="/base.css" data-precedence="0" />
+                            <title>{"Blog"}</title>
+                        </>
+                    },
+                ),
+            ]
+            .into(),
+        };
+        let keys: Vec<String> = state.resolve().iter().filter_map(head_key).collect();
+        assert_eq!(
+            keys,
+            [
+                "charset",
+                "title",
+                "stylesheet:/base.css",
+                "stylesheet:/page.css"
+            ]
+        );
+    }
+
+    #[test]
+    fn head_elements_are_keyed_uniquely() {
+        let head = key_tags(map_components(vec![rsx! {
+            <>
+                <link rel="stylesheet" href="/blog.css" />
+                <meta property="og:image" content="/a.png" />
+                <meta property="og:image:width" content="1200" />
+                <meta property="og:image" content="/b.png" />
+                <meta property="og:image:width" content="1200" />
+            </>
+        }]));
+        let keys: Vec<String> = head
+            .iter()
+            .filter_map(|html| match html {
+                Html::VTag(tag) => tag.key.as_ref().map(|key| key.to_string()),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(keys.len(), head.len());
+        assert!(keys.contains(&"stylesheet:/blog.css#0".to_string()));
+        let widths: Vec<&String> = keys
+            .iter()
+            .filter(|key| key.contains("og:image:width"))
+            .collect();
+        assert_eq!(widths.len(), 2);
+        assert_ne!(widths[0], widths[1]);
+    }
 }
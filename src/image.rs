@@ -1,9 +1,36 @@
 use crate::prelude::*;
+use crate::resource_hints::preload;
 use gloo_net::http::Request;
 use wasm_bindgen_futures::spawn_local;
-use web_sys::js_sys::Function;
-use web_sys::wasm_bindgen::JsValue;
-use web_sys::{IntersectionObserver, IntersectionObserverInit, RequestCache};
+use web_sys::js_sys::Array;
+use web_sys::wasm_bindgen::closure::Closure;
+use web_sys::wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    HtmlImageElement, IntersectionObserver, IntersectionObserverEntry, IntersectionObserverInit,
+    RequestCache,
+};
+
+/// The stylesheet of the classes used by `Image` with `styling="class"`, to be served with the
+/// other stylesheets of the site.
+pub const IMAGE_CSS: &str = "\
+.next-rs-image-wrapper { display: block; position: relative; }
+.next-rs-image-fill { position: absolute; top: 0; left: 0; bottom: 0; right: 0; }
+.next-rs-image-responsive > img { width: 100%; height: auto; }
+.next-rs-image-intrinsic { display: inline-block; max-width: 100%; }
+.next-rs-image-intrinsic > img { max-width: 100%; height: auto; }
+.next-rs-image-fixed { display: inline-block; }
+.next-rs-image-fill > img { width: 100%; height: 100%; }
+.next-rs-fit-contain { object-fit: contain; }
+.next-rs-fit-cover { object-fit: cover; }
+.next-rs-fit-fill { object-fit: fill; }
+.next-rs-fit-none { object-fit: none; }
+.next-rs-fit-scale-down { object-fit: scale-down; }
+.next-rs-position-center { object-position: center; }
+.next-rs-position-top { object-position: top; }
+.next-rs-position-bottom { object-position: bottom; }
+.next-rs-position-left { object-position: left; }
+.next-rs-position-right { object-position: right; }
+";
 
 /// Properties for the Image component.
 #[derive(Properties, Clone, PartialEq)]
@@ -42,7 +69,7 @@ pub struct ImageProps {
     pub quality: &'static str,
 
     #[prop_or_default]
-    /// Indicates if the image should have priority loading.
+    /// Indicates if the image should have priority loading, preloading it from `<head>`.
     pub priority: bool,
 
     #[prop_or_default]
@@ -90,6 +117,11 @@ pub struct ImageProps {
     /// Reference to the DOM node.
     pub node_ref: NodeRef,
 
+    #[prop_or("inline")]
+    /// How the layout is styled. Valid values: "inline" for `style` attributes, or "class" for the
+    /// classes of [`IMAGE_CSS`], which a strict Content Security Policy allows.
+    pub styling: &'static str,
+
     #[prop_or_default]
     /// Indicates the current state of the image in a navigation menu. Valid values: "page", "step", "location", "date", "time", "true", "false".
     pub aria_current: &'static str,
@@ -146,6 +178,7 @@ impl Default for ImageProps {
             unoptimized: false,
             layout: "responsive",
             node_ref: NodeRef::default(),
+            styling: "inline",
             aria_current: "",
             aria_describedby: "",
             aria_expanded: "",
@@ -210,63 +243,65 @@ pub fn Image(props: &ImageProps) -> Html {
     let props = props.clone();
     let img_ref = props.node_ref.clone();
 
-    use_effect_with(JsValue::from(props.src), move |deps| {
-        // Define the callback function for the IntersectionObserver
-        let callback = Function::new_no_args(
-            r###"
-            {
-                let img_ref = img_ref.clone();
-                let on_loading_complete = props.on_loading_complete.clone();
-                let on_error = props.on_error.clone();
-                
-                move || {
-                    let entries: Vec<web_sys::IntersectionObserverEntry> = js_sys::try_iter(deps)
-                        .unwrap()
-                        .unwrap()
-                        .map(|v| v.unwrap().unchecked_into())
-                        .collect();
-
-                    // Check if the image is intersecting with the viewport
-                    if let Some(entry) = entries.get(0) {
-                        if entry.is_intersecting() {
-                            // Load the image when it becomes visible
-                            let img: HtmlImageElement = img_ref.cast().unwrap();
-                            img.set_src(&props.src);
-
-                            // Call the loading complete callback
-                            on_loading_complete.emit(());
-                        }
+    // Priority images are above the fold, so start downloading them from `<head>`.
+    let hint = match props.priority {
+        true => preload(props.src, "image"),
+        false => Html::default(),
+    };
+
+    {
+        let on_loading_complete = props.on_loading_complete.clone();
+        use_effect_with(props.src, move |src| {
+            let src = *src;
+            let target = img_ref.cast::<web_sys::HtmlElement>();
+
+            // Load the image once it intersects with the viewport
+            let callback = Closure::<dyn FnMut(Array)>::new(move |entries: Array| {
+                let intersecting = entries.iter().any(|entry| {
+                    entry
+                        .unchecked_into::<IntersectionObserverEntry>()
+                        .is_intersecting()
+                });
+                if intersecting {
+                    if let Some(img) = img_ref.cast::<HtmlImageElement>() {
+                        img.set_src(src);
+                        on_loading_complete.emit(());
                     }
                 }
+            });
+
+            // Create IntersectionObserver configuration
+            let mut options = IntersectionObserverInit::new();
+            options.threshold(&JsValue::from(0.0));
+            options.root(
+                web_sys::window()
+                    .and_then(|win| win.document())
+                    .and_then(|doc| doc.body())
+                    .as_ref()
+                    .map(|body| body.as_ref()),
+            );
+
+            // Create IntersectionObserver instance
+            let observer =
+                IntersectionObserver::new_with_options(callback.as_ref().unchecked_ref(), &options)
+                    .ok();
+
+            // Observe the image element
+            if let (Some(observer), Some(img)) = (&observer, &target) {
+                observer.observe(img);
+            }
+
+            // Cleanup: Disconnect the IntersectionObserver when the component unmounts
+            move || {
+                if let Some(observer) = observer {
+                    observer.disconnect();
+                }
+                drop(callback);
             }
-            "###,
-        );
-
-        // Create IntersectionObserver configuration
-        let mut options = IntersectionObserverInit::new();
-        options.threshold(deps);
-        options.root(Some(
-            &web_sys::window()
-                .and_then(|win| win.document())
-                .unwrap()
-                .body()
-                .unwrap(),
-        ));
-
-        // Create IntersectionObserver instance
-        let observer = IntersectionObserver::new_with_options(&callback, &options)
-            .expect("Failed to create IntersectionObserver");
-
-        // Observe the image element
-        if let Some(img) = img_ref.cast::<web_sys::HtmlElement>() {
-            observer.observe(&img);
-        }
+        });
+    }
 
-        // Cleanup: Disconnect the IntersectionObserver when the component unmounts
-        return move || {
-            observer.disconnect();
-        };
-    });
+    let class_props = (props.styling == "class").then(|| props.clone());
 
     let fetch_data = {
         Callback::from(move |_| {
@@ -310,6 +345,15 @@ pub fn Image(props: &ImageProps) -> Html {
         })
     };
 
+    if let Some(props) = class_props {
+        return rsx! {
+            <>
+                {hint}
+                {class_image(&props, fetch_data)}
+            </>
+        };
+    }
+
     let img_style = {
         let mut style = String::new();
         if !props.object_fit.is_empty() {
@@ -512,6 +556,58 @@ pub fn Image(props: &ImageProps) -> Html {
         }
     };
     rsx! {
+        <>
+            {hint}
             {layout}
+        </>
+    }
+}
+
+/// Renders an image styled with the classes of [`IMAGE_CSS`] instead of `style` attributes.
+///
+/// Responsive images keep their aspect ratio from their `width` and `height` attributes, and the
+/// blur placeholder, which needs an inline background, is not rendered.
+fn class_image(props: &ImageProps, on_error: Callback<Event>) -> Html {
+    let layout = match props.layout {
+        "fill" | "responsive" | "intrinsic" | "fixed" => props.layout,
+        _ => "wrapper",
+    };
+    let wrapper_class = classes!("next-rs-image-wrapper", format!("next-rs-image-{}", layout));
+    let img_class = classes!(
+        (!props.object_fit.is_empty()).then(|| format!("next-rs-fit-{}", props.object_fit)),
+        (!props.object_position.is_empty())
+            .then(|| format!("next-rs-position-{}", props.object_position)),
+        props.class
+    );
+    let (width, height) = match props.layout {
+        "fill" => ("", ""),
+        _ => (props.width, props.height),
+    };
+
+    rsx! {
+        <span class={wrapper_class}>
+            <img
+                src={props.src}
+                alt={props.alt}
+                width={width}
+                height={height}
+                class={img_class}
+                loading={if props.priority { "eager" } else { "lazy" }}
+                sizes={props.sizes}
+                decoding={props.decoding}
+                ref={props.node_ref.clone()}
+                role="img"
+                aria-label={props.alt}
+                aria-labelledby={props.aria_labelledby}
+                aria-describedby={props.aria_describedby}
+                aria-hidden={props.aria_hidden}
+                aria-current={props.aria_current}
+                aria-expanded={props.aria_expanded}
+                aria-live={props.aria_live}
+                aria-pressed={props.aria_pressed}
+                aria-controls={props.aria_controls}
+                onerror={on_error}
+            />
+        </span>
     }
 }
@@ -0,0 +1,419 @@
+use crate::head::Head;
+use crate::log;
+use crate::prelude::*;
+use serde::Serialize;
+use serde_json::Value;
+
+// The vocabulary of structured data that doesn't set its own `@context`.
+static SCHEMA_CONTEXT: &str = "https://schema.org";
+
+/// Escapes serialized JSON so it can't close or comment out its `<script>` element.
+///
+/// `<`, `>` and `&` are written as unicode escapes, which only occur inside JSON strings, and so
+/// are line and paragraph separators, which older JavaScript parsers reject.
+///
+/// # Example
+/// ```
+/// use next_rs::json_ld::escape_json;
+///
+/// let json = escape_json(r#"{"name":"</script><script>alert(1)</script>"}"#);
+/// assert!(!json.contains("</script>"));
+/// ```
+pub fn escape_json(json: &str) -> String {
+    let mut escaped = String::with_capacity(json.len());
+    for c in json.chars() {
+        match c {
+            '<' => escaped.push_str("\\u003c"),
+            '>' => escaped.push_str("\\u003e"),
+            '&' => escaped.push_str("\\u0026"),
+            '\u{2028}' => escaped.push_str("\\u2028"),
+            '\u{2029}' => escaped.push_str("\\u2029"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Renders structured data as a `<script type="application/ld+json">` element.
+///
+/// The `https://schema.org` context is added to objects without one, and the `@id` of the data is
+/// kept in a `data-ld-id` attribute, so the head manager keeps a single script per `@id`.
+///
+/// # Returns
+///
+/// (Html): The script element, or nothing if the data can't be serialized.
+pub fn json_ld<T: Serialize + ?Sized>(data: &T) -> Html {
+    let mut value = match serde_json::to_value(data) {
+        Ok(value) => value,
+        Err(err) => {
+            if cfg!(target_arch = "wasm32") {
+                log(&format!("Error serializing JSON-LD: {}", err).into());
+            }
+            return Html::default();
+        }
+    };
+
+    if let Value::Object(object) = &mut value {
+        object
+            .entry("@context")
+            .or_insert_with(|| SCHEMA_CONTEXT.into());
+    }
+    let id = value
+        .get("@id")
+        .and_then(Value::as_str)
+        .map(|id| AttrValue::from(id.to_string()));
+
+    rsx! {
+        <script type="application/ld+json" data-ld-id={id}>
+            {escape_json(&value.to_string())}
+        </script>
+    }
+}
+
+/// Props for [`JsonLd`].
+#[derive(Properties, Clone, PartialEq)]
+pub struct JsonLdProps<T: PartialEq> {
+    /// The structured data, such as a [`Product`] or any other serializable value.
+    pub data: T,
+}
+
+/// A component rendering structured data into `<head>`.
+///
+/// Scripts sharing an `@id` are deduplicated, the most deeply nested one winning.
+///
+/// # Example
+/// ```rust
+/// use next_rs::prelude::*;
+/// use next_rs::json_ld::{FaqPage, JsonLd, Question};
+///
+/// #[func]
+/// pub fn Faq() -> Html {
+///     let faq = FaqPage::new(vec![
+///         Question::new("Is Next RS free?", "Yes, it is MIT licensed."),
+///     ]);
+///
+///     rsx! {
+///         <JsonLd<FaqPage> data={faq} />
+///     }
+/// }
+/// ```
+#[func]
+pub fn JsonLd<T>(props: &JsonLdProps<T>) -> Html
+where
+    T: Serialize + PartialEq + Clone + 'static,
+{
+    rsx! {
+        <Head>{json_ld(&props.data)}</Head>
+    }
+}
+
+/// A person, such as the author of an article.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct Person {
+    /// The name of the person.
+    pub name: String,
+    /// The URL of the person's profile.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl Person {
+    /// Creates a person from their name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            url: None,
+        }
+    }
+}
+
+/// An organization, such as the publisher of a site.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct Organization {
+    /// The identifier of the organization, usually its URL with a fragment.
+    #[serde(rename = "@id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The name of the organization.
+    pub name: String,
+    /// The URL of the organization.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The URL of the organization's logo.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logo: Option<String>,
+    /// The URLs of the organization's profiles on other sites.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub same_as: Vec<String>,
+}
+
+/// A website, enabling site name and search features.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct WebSite {
+    /// The identifier of the website.
+    #[serde(rename = "@id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The name of the website.
+    pub name: String,
+    /// The URL of the website.
+    pub url: String,
+}
+
+/// An article, such as a blog post or a news story.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct Article {
+    /// The identifier of the article.
+    #[serde(rename = "@id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The headline of the article.
+    pub headline: String,
+    /// The description of the article.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The URLs of the article's images.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub image: Vec<String>,
+    /// When the article was first published, as an ISO 8601 date time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_published: Option<String>,
+    /// When the article was last changed, as an ISO 8601 date time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_modified: Option<String>,
+    /// The authors of the article.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub author: Vec<Person>,
+    /// The publisher of the article.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<Organization>,
+}
+
+/// The brand of a product.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct Brand {
+    /// The name of the brand.
+    pub name: String,
+}
+
+/// An offer to sell a product.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct Offer {
+    /// The price, such as `19.99`.
+    pub price: String,
+    /// The currency of the price, as an ISO 4217 code such as `USD`.
+    pub price_currency: String,
+    /// The availability, such as `https://schema.org/InStock`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub availability: Option<String>,
+    /// The URL where the product can be bought.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl Offer {
+    /// Creates an offer from a price and its currency.
+    pub fn new(price: impl Into<String>, price_currency: impl Into<String>) -> Self {
+        Self {
+            price: price.into(),
+            price_currency: price_currency.into(),
+            ..Self::default()
+        }
+    }
+}
+
+/// A product, enabling price and availability in search results.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct Product {
+    /// The identifier of the product.
+    #[serde(rename = "@id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The name of the product.
+    pub name: String,
+    /// The description of the product.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The URLs of the product's images.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub image: Vec<String>,
+    /// The stock keeping unit of the product.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sku: Option<String>,
+    /// The brand of the product.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub brand: Option<Brand>,
+    /// The offers to sell the product.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub offers: Vec<Offer>,
+}
+
+/// The accepted answer of a [`Question`].
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct Answer {
+    /// The text of the answer.
+    pub text: String,
+}
+
+/// A question of a [`FaqPage`].
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct Question {
+    /// The text of the question.
+    pub name: String,
+    /// The accepted answer.
+    pub accepted_answer: Answer,
+}
+
+impl Question {
+    /// Creates a question with its accepted answer.
+    pub fn new(name: impl Into<String>, answer: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            accepted_answer: Answer {
+                text: answer.into(),
+            },
+        }
+    }
+}
+
+/// A page of frequently asked questions.
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(tag = "@type", rename = "FAQPage", rename_all = "camelCase")]
+pub struct FaqPage {
+    /// The identifier of the page.
+    #[serde(rename = "@id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The questions of the page.
+    pub main_entity: Vec<Question>,
+}
+
+impl FaqPage {
+    /// Creates a page from its questions.
+    pub fn new(questions: Vec<Question>) -> Self {
+        Self {
+            id: None,
+            main_entity: questions,
+        }
+    }
+}
+
+/// An item of a [`BreadcrumbList`].
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct ListItem {
+    /// The position of the item, starting at 1.
+    pub position: usize,
+    /// The name of the item.
+    pub name: String,
+    /// The URL of the item.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item: Option<String>,
+}
+
+/// The breadcrumb trail of a page.
+///
+/// # Example
+/// ```
+/// use next_rs::json_ld::BreadcrumbList;
+///
+/// let breadcrumbs = BreadcrumbList::new([
+///     ("Blog", "https://acme.dev/blog"),
+///     ("Shipping Next RS", "https://acme.dev/blog/shipping"),
+/// ]);
+/// assert_eq!(breadcrumbs.item_list_element[1].position, 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(tag = "@type", rename_all = "camelCase")]
+pub struct BreadcrumbList {
+    /// The items of the trail, from the root.
+    pub item_list_element: Vec<ListItem>,
+}
+
+impl BreadcrumbList {
+    /// Creates a trail from the names and URLs of its items, from the root.
+    pub fn new<N, U>(items: impl IntoIterator<Item = (N, U)>) -> Self
+    where
+        N: Into<String>,
+        U: Into<String>,
+    {
+        let item_list_element = items
+            .into_iter()
+            .enumerate()
+            .map(|(index, (name, url))| ListItem {
+                position: index + 1,
+                name: name.into(),
+                item: Some(url.into()),
+            })
+            .collect();
+        Self { item_list_element }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::head::{head_key, text_content, unique};
+
+    #[test]
+    fn escape_json_neutralizes_script_and_comment_delimiters() {
+        let json = escape_json(r#"{"name":"</script><!-- & -->"}"#);
+        assert_eq!(
+            json,
+            r#"{"name":"\u003c/script\u003e\u003c!-- \u0026 --\u003e"}"#
+        );
+        assert!(!json.contains("</script>") && !json.contains("<!--"));
+    }
+
+    #[test]
+    fn escape_json_escapes_line_and_paragraph_separators() {
+        let json = escape_json("{\"name\":\"a\u{2028}b\u{2029}c\"}");
+        assert_eq!(json, r#"{"name":"a\u2028b\u2029c"}"#);
+    }
+
+    #[test]
+    fn json_ld_adds_the_schema_context() {
+        let html = json_ld(&Brand {
+            name: "Acme".into(),
+        });
+        assert_eq!(
+            text_content(&html),
+            r#"{"@context":"https://schema.org","@type":"Brand","name":"Acme"}"#
+        );
+    }
+
+    #[test]
+    fn scripts_sharing_an_id_are_deduplicated() {
+        let organization = |name: &str, id: &str| Organization {
+            id: Some(id.into()),
+            name: name.into(),
+            ..Organization::default()
+        };
+        let site = WebSite {
+            name: "Acme".into(),
+            url: "https://acme.dev".into(),
+            ..WebSite::default()
+        };
+
+        let head = unique(vec![
+            json_ld(&organization("Acme", "https://acme.dev/#org")),
+            json_ld(&site),
+            json_ld(&organization("Acme Inc.", "https://acme.dev/#org")),
+            json_ld(&organization("Acme Labs", "https://acme.dev/#labs")),
+        ]);
+        let keys: Vec<Option<String>> = head.iter().map(head_key).collect();
+        assert_eq!(
+            keys,
+            [
+                Some("json-ld:https://acme.dev/#org".to_string()),
+                None,
+                Some("json-ld:https://acme.dev/#labs".to_string()),
+            ]
+        );
+        assert!(text_content(&head[0]).contains("Acme Inc."));
+    }
+}
@@ -20,6 +20,9 @@
 //! | `i18n`         | `yew-i18n`               | [![GitHub](https://img.shields.io/github/stars/next-rs/yew-i18n)](https://github.com/next-rs/yew-i18n)             | Implement internationalization for multi-language support.  |
 //! | `input`        | `input_yew`              | [![GitHub](https://img.shields.io/github/stars/next-rs/input-yew)](https://github.com/next-rs/input-yew)        | Utilize custom input components for enhanced form handling. |
 //! | `css`          | `stylist`                | [![GitHub](https://img.shields.io/github/stars/futursolo/stylist-rs)](https://github.com/futursolo/stylist-rs)           | Apply styling to your components using the Stylist crate integration.|
+//! | `ssr`          | `yew/ssr`                | [![GitHub](https://img.shields.io/github/stars/yewstack/yew)](https://github.com/yewstack/yew)                       | Render on the server, collecting `Head` elements with a `HeadCollector`. |
+//! | `hydration`    | `yew/hydration`          | [![GitHub](https://img.shields.io/github/stars/yewstack/yew)](https://github.com/yewstack/yew)                       | Hydrate pages rendered by `Document::render` instead of replacing them. |
+//! | `og-png`       | `resvg`, `ttf-parser`    | [![GitHub](https://img.shields.io/github/stars/linebender/resvg)](https://github.com/linebender/resvg)               | Rasterize Open Graph images to PNG in a native build step. |
 //!
 //! To use a specific feature, enable it using the `features` configuration in your `Cargo.toml` file:
 //!
@@ -75,17 +78,46 @@
 //! Special thanks to the Yew community and contributors for such an amazing framework.
 //!
 
+pub mod alternates;
+pub mod attributes;
+pub mod csp;
+pub mod document;
+pub mod dynamic;
+pub mod feed;
 pub mod head;
 pub mod image;
+pub mod json_ld;
 pub mod link;
+pub mod manifest;
+pub mod metadata;
+pub mod og_image;
+pub mod open_graph;
+pub mod resource_hints;
+pub mod robots;
+pub mod route_meta;
 pub mod router;
+pub mod script;
+pub mod sitemap;
+pub mod stylesheet;
+pub mod testing;
 
-pub use head::Head;
+pub use alternates::{Alternates, AlternatesProps};
+pub use attributes::{AttributesProps, BodyAttributes, HtmlAttributes};
+pub use csp::{use_nonce, CspConfig, CspProvider};
+pub use document::Document;
+pub use dynamic::{Dynamic, DynamicProps, LazyRoute};
+pub use head::{Head, HeadCollector, HeadProvider, Metadata, MetadataProvider};
 pub use image::{Image, ImageProps};
 #[cfg(feature = "input")]
 pub use input_yew::CustomInput as Input;
-pub use link::{Link, LinkProps};
+pub use json_ld::{JsonLd, JsonLdProps};
+pub use link::{ActiveLinks, ActiveLinksProps, Link, LinkProps};
+pub use metadata::use_metadata;
+pub use open_graph::{OpenGraph, TwitterCard};
+pub use route_meta::{use_route_meta, Route, RouteMeta};
 pub use router::*;
+pub use script::{Script, ScriptProps};
+pub use stylesheet::{Stylesheet, StylesheetProps};
 #[cfg(feature = "css")]
 pub use stylist::yew::styled_component;
 pub use web_sys::console::log_1 as log;
@@ -1,20 +1,32 @@
+use crate::dynamic::lazy_route_for;
+use crate::log;
 use crate::prelude::*;
 use crate::router::*;
 use serde_json::Value;
+use std::borrow::Cow;
+use web_sys::wasm_bindgen::JsCast;
 use web_sys::window;
-use web_sys::{ScrollBehavior, ScrollToOptions};
+use web_sys::{Element, ScrollBehavior, ScrollToOptions};
 
 /// Properties for the Link component.
 #[derive(Properties, Clone, PartialEq)]
 pub struct LinkProps {
-    /// The target URL for the link.
+    /// The target URL for the link, either a string or a [`crate::router::Url`].
     #[prop_or_default]
-    pub to: &'static str,
+    pub to: Href,
 
     /// The CSS class for styling the link.
     #[prop_or_default]
     pub class: &'static str,
 
+    /// The CSS classes appended to `class` while the link points at the current route.
+    #[prop_or_default]
+    pub active_class: AttrValue,
+
+    /// Only mark the link as active when the whole route matches, instead of a route prefix.
+    #[prop_or_default]
+    pub exact: bool,
+
     /// The target attribute for the link.
     #[prop_or("_blank")]
     pub target: &'static str,
@@ -40,6 +52,7 @@ pub struct LinkProps {
     pub scroll: bool,
 
     /// Enable automatic prefetch of components before clicking the link.
+    /// Links to lazy routes always warm their chunk on hover.
     #[prop_or_default]
     pub prefetch: bool,
 
@@ -52,6 +65,7 @@ pub struct LinkProps {
     pub scroll_behavior: &'static str,
 
     /// Indicates the current state of the link in a navigation menu. Valid values: "page", "step", "location", "date", "time", "true", "false".
+    /// Defaults to "page" while the link is active.
     #[prop_or_default]
     pub aria_current: &'static str,
 
@@ -86,6 +100,9 @@ pub struct LinkProps {
 
 /// The Link component is used for creating accessible links with additional features.
 ///
+/// Internal paths, such as `/blog`, are rendered prefixed with the basename of the router, such as
+/// `/docs/blog`, so that they also work when opened in a new tab.
+///
 /// # Arguments
 /// * `props` - The properties of the component.
 ///
@@ -113,78 +130,96 @@ pub struct LinkProps {
 #[func]
 pub fn Link(props: &LinkProps) -> Html {
     let props = props.clone();
-    let to = props.to;
+    let to = props.to.clone();
     #[allow(unused_variables)]
     let state = props.state;
     #[allow(unused_variables)]
-    let query = props.query;
+    let query = props.query.clone();
     let router = use_router();
     let router_clone = router.clone();
-    let (target, href) = if props.to.starts_with("/#") {
+    let is_active = is_active_path(&use_route(), &to, props.exact);
+    let (target, href): (&'static str, AttrValue) = if to.starts_with("/#") {
         // local anchor
-        ("_self", &props.to[1..])
-    } else if props.to.starts_with('#') {
+        ("_self", to[1..].to_string().into())
+    } else if to.starts_with('#') {
         // also local anchor
-        ("_self", props.to)
+        ("_self", to.clone().into())
     } else {
         // external
-        (props.target, props.to)
+        (props.target, to.clone().into())
     };
-    let onclick = Callback::from(move |event: MouseEvent| {
-        let mut router = router.clone();
-        let query = query.clone();
-        match (props.state, query) {
-            ("", Value::Null) => {
-                // Don't push the url twice onto the stack
-                if target != "_blank" {
-                    router.push(to);
+    let onclick = {
+        let to = to.clone();
+        let href = href.clone();
+        Callback::from(move |event: MouseEvent| {
+            let mut router = router.clone();
+            let query = query.clone();
+            match (props.state, query) {
+                ("", Value::Null) => {
+                    // Don't push the url twice onto the stack
+                    if target != "_blank" {
+                        router.push(to.clone());
+                    }
+                }
+                (state, Value::Null) => {
+                    event.prevent_default();
+                    router.push_with_state(to.clone(), state);
+                }
+                ("", query) => {
+                    event.prevent_default();
+                    if let Err(err) = router.try_push_with_query(to.clone(), &query) {
+                        log(&format!("Failed to follow the link to {}: {}", &*to, err).into());
+                    }
+                }
+                (state, query) => {
+                    event.prevent_default();
+                    if let Err(err) =
+                        router.try_push_with_query_and_state(to.clone(), &query, state)
+                    {
+                        log(&format!("Failed to follow the link to {}: {}", &*to, err).into());
+                    }
                 }
             }
-            (state, Value::Null) => {
-                event.prevent_default();
-                router.push_with_state(to, state);
-            }
-            ("", query) => {
-                event.prevent_default();
-                router
-                    .push_with_query(to, &query)
-                    .expect("failed push history with query");
-            }
-            (state, query) => {
-                event.prevent_default();
-                router
-                    .push_with_query_and_state(to, &query, state)
-                    .expect("failed push history with query and state");
-            }
-        }
-        if props.scroll {
-            let scroll_behavior = match props.scroll_behavior {
-                "auto" => ScrollBehavior::Auto,
-                "instant" => ScrollBehavior::Instant,
-                "smooth" => ScrollBehavior::Smooth,
-                _ => ScrollBehavior::Auto,
-            };
-
-            if props.to.starts_with('#') || props.to.starts_with("/#") {
-                // Prevent default navigation behavior("instant")
-                event.prevent_default();
-                // Local anchor link
-                if let Some(element) = window()
-                    .and_then(|win| win.document())
-                    .and_then(|doc| doc.get_element_by_id(&href[1..]))
-                {
-                    let offset_top = element.get_bounding_client_rect().y();
-                    window()
-                        .map(|win| {
-                            win.scroll_to_with_scroll_to_options(
-                                ScrollToOptions::new()
-                                    .top(offset_top)
-                                    .behavior(scroll_behavior),
-                            )
-                        })
-                        .expect("Failed to scroll to local anchor link");
+            if props.scroll {
+                let scroll_behavior = match props.scroll_behavior {
+                    "auto" => ScrollBehavior::Auto,
+                    "instant" => ScrollBehavior::Instant,
+                    "smooth" => ScrollBehavior::Smooth,
+                    _ => ScrollBehavior::Auto,
+                };
+
+                if to.starts_with('#') || to.starts_with("/#") {
+                    // Prevent default navigation behavior("instant")
+                    event.prevent_default();
+                    // Local anchor link
+                    if let Some(element) = window()
+                        .and_then(|win| win.document())
+                        .and_then(|doc| doc.get_element_by_id(&href[1..]))
+                    {
+                        let offset_top = element.get_bounding_client_rect().y();
+                        window()
+                            .map(|win| {
+                                win.scroll_to_with_scroll_to_options(
+                                    ScrollToOptions::new()
+                                        .top(offset_top)
+                                        .behavior(scroll_behavior),
+                                )
+                            })
+                            .expect("Failed to scroll to local anchor link");
+                    } else {
+                        // Fallback to prop offset if element is not found
+                        window()
+                            .map(|win| {
+                                win.scroll_to_with_scroll_to_options(
+                                    ScrollToOptions::new()
+                                        .top(props.scroll_offset)
+                                        .behavior(scroll_behavior),
+                                )
+                            })
+                            .expect("Failed to scroll to fallback offset");
+                    }
                 } else {
-                    // Fallback to prop offset if element is not found
+                    // External link
                     window()
                         .map(|win| {
                             win.scroll_to_with_scroll_to_options(
@@ -193,45 +228,63 @@ pub fn Link(props: &LinkProps) -> Html {
                                     .behavior(scroll_behavior),
                             )
                         })
-                        .expect("Failed to scroll to fallback offset");
+                        .expect("Failed to scroll to external link");
                 }
-            } else {
-                // External link
-                window()
-                    .map(|win| {
-                        win.scroll_to_with_scroll_to_options(
-                            ScrollToOptions::new()
-                                .top(props.scroll_offset)
-                                .behavior(scroll_behavior),
-                        )
-                    })
-                    .expect("Failed to scroll to external link");
             }
-        }
-    });
-    use_effect_with((), move |_| {
-        if props.prefetch {
-            let mut router = router_clone.clone();
-            router.prefetch(href);
-        }
-    });
-    let aria_label = "Link to ".to_string() + href;
+        })
+    };
+    let onmouseenter = {
+        let router = router_clone.clone();
+        let to = to.clone();
+        Callback::from(move |_: MouseEvent| {
+            if lazy_route_for(&to).is_some() {
+                let mut router = router.clone();
+                router.prefetch(to.clone());
+            }
+        })
+    };
+    let rendered_href = with_basename(router_clone.basename(), &href);
+    {
+        let href = href.clone();
+        use_effect_with((), move |_| {
+            if props.prefetch {
+                let mut router = router_clone.clone();
+                router.prefetch(href);
+            }
+        });
+    }
+    let aria_label = format!("Link to {}", href);
 
     let tabindex = if props.scroll { "0" } else { "-1" };
 
+    let class = if is_active && !props.active_class.is_empty() {
+        format!("{} {}", props.class, props.active_class)
+            .trim()
+            .to_string()
+    } else {
+        props.class.to_string()
+    };
+
+    let aria_current = if props.aria_current.is_empty() && is_active {
+        "page"
+    } else {
+        props.aria_current
+    };
+
     rsx! {
         <a
-            href={href}
+            href={rendered_href}
             target={target}
             rel={props.rel}
-            class={props.class}
+            class={class}
             onclick={onclick}
+            onmouseenter={onmouseenter}
             role="link"
             tabindex={tabindex}
             aria-label={aria_label.clone()}
             title={aria_label.clone()}
             aria-haspopup="true"
-            aria-current={props.aria_current}
+            aria-current={aria_current}
             aria-describedby={props.aria_describedby}
             aria-expanded={props.aria_expanded}
             aria-hidden={props.aria_hidden}
@@ -242,3 +295,159 @@ pub fn Link(props: &LinkProps) -> Html {
         >{ props.children.clone() }</a>
     }
 }
+
+/// Properties for the ActiveLinks component.
+#[derive(Properties, Clone, PartialEq)]
+pub struct ActiveLinksProps {
+    /// The menu whose anchors should be highlighted.
+    #[prop_or_default]
+    pub children: Html,
+
+    /// The CSS class for styling the wrapper element.
+    #[prop_or_default]
+    pub class: &'static str,
+
+    /// The CSS classes added to every anchor that points at the current route.
+    #[prop_or(AttrValue::Static("active"))]
+    pub active_class: AttrValue,
+
+    /// Only mark anchors as active when the whole route matches, instead of a route prefix.
+    #[prop_or_default]
+    pub exact: bool,
+}
+
+/// Highlights the anchors of a menu that point at the current route.
+///
+/// Menus such as `Navbar` and `Sidebar` render plain anchors, so this component marks them from
+/// the outside: whenever the route changes, every `<a href>` inside it gets `active_class` and
+/// `aria-current="page"` if it matches the route, and loses them otherwise.
+///
+/// # Arguments
+/// * `props` - The properties of the component.
+///
+/// # Returns
+/// (Html): An HTML representation of the wrapped menu.
+///
+/// # Examples
+/// ```
+/// use next_rs::prelude::*;
+/// use next_rs::ActiveLinks;
+///
+/// #[func]
+/// pub fn MyMenu() -> Html {
+///
+///     rsx! {
+///         <ActiveLinks active_class="text-blue-400">
+///             <a href="/">{ "Home" }</a>
+///             <a href="/blog">{ "Blog" }</a>
+///         </ActiveLinks>
+///     }
+/// }
+/// ```
+#[func]
+pub fn ActiveLinks(props: &ActiveLinksProps) -> Html {
+    let node_ref = use_node_ref();
+    let router = use_router();
+    let route = use_route();
+
+    {
+        let node_ref = node_ref.clone();
+        let active_class = props.active_class.clone();
+        let exact = props.exact;
+        use_effect_with((route, props.children.clone()), move |(route, _)| {
+            let anchors = node_ref
+                .cast::<Element>()
+                .and_then(|container| container.query_selector_all("a[href]").ok());
+
+            if let Some(anchors) = anchors {
+                for index in 0..anchors.length() {
+                    let anchor = match anchors
+                        .get(index)
+                        .and_then(|node| node.dyn_into::<Element>().ok())
+                    {
+                        Some(anchor) => anchor,
+                        None => continue,
+                    };
+                    let href = anchor.get_attribute("href").unwrap_or_default();
+                    let href = router.strip_basename(Cow::Owned(href));
+                    let is_active = is_active_path(route, &href, exact);
+                    let classes = anchor.get_attribute("class").unwrap_or_default();
+                    let classes = toggle_classes(&classes, &active_class, is_active);
+
+                    if is_active {
+                        let _ = anchor.set_attribute("aria-current", "page");
+                    } else {
+                        let _ = anchor.remove_attribute("aria-current");
+                    }
+                    let _ = anchor.set_attribute("class", &classes);
+                }
+            }
+        });
+    }
+
+    rsx! {
+        <div ref={node_ref} class={props.class}>{ props.children.clone() }</div>
+    }
+}
+
+/// Adds every class of `active_class` to `classes` if `active`, and removes them otherwise.
+fn toggle_classes(classes: &str, active_class: &str, active: bool) -> String {
+    let active_classes: Vec<&str> = active_class.split_whitespace().collect();
+    let mut classes: Vec<&str> = classes
+        .split_whitespace()
+        .filter(|class| !active_classes.contains(class))
+        .collect();
+    if active {
+        classes.extend(active_classes);
+    }
+    classes.join(" ")
+}
+
+/// Prefixes an internal path with the basename of the router.
+fn with_basename(basename: &str, href: &AttrValue) -> AttrValue {
+    let basename = basename.strip_suffix('/').unwrap_or(basename);
+    match href.starts_with('/') && !href.starts_with("//") {
+        true => format!("{}{}", basename, href).into(),
+        false => href.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn internal_paths_are_prefixed_with_the_basename() {
+        let href = |href: &'static str| AttrValue::from(href);
+        assert_eq!(
+            with_basename("/docs", &href("/blog?page=2")),
+            "/docs/blog?page=2"
+        );
+        assert_eq!(with_basename("/docs/", &href("/blog")), "/docs/blog");
+        assert_eq!(with_basename("", &href("/blog")), "/blog");
+        assert_eq!(with_basename("/docs", &href("#about")), "#about");
+        assert_eq!(
+            with_basename("/docs", &href("//cdn.example.com/a.js")),
+            "//cdn.example.com/a.js"
+        );
+        assert_eq!(
+            with_basename("/docs", &href("https://example.com")),
+            "https://example.com"
+        );
+    }
+
+    #[test]
+    fn active_classes_do_not_pile_up_across_navigations() {
+        let active_class = "text-blue-400 font-bold";
+
+        // Navigate to the link, away from it, then back to it.
+        let classes = toggle_classes("nav-link", active_class, true);
+        assert_eq!(classes, "nav-link text-blue-400 font-bold");
+        let classes = toggle_classes(&classes, active_class, true);
+        assert_eq!(classes, "nav-link text-blue-400 font-bold");
+        let classes = toggle_classes(&classes, active_class, false);
+        assert_eq!(classes, "nav-link");
+        let classes = toggle_classes(&classes, active_class, true);
+        assert_eq!(classes, "nav-link text-blue-400 font-bold");
+    }
+}
@@ -0,0 +1,409 @@
+use crate::head::Head;
+use crate::metadata::{Icon, Metadata};
+use crate::prelude::*;
+use serde::Serialize;
+
+/// An icon of a [`WebManifest`].
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct ManifestIcon {
+    /// The URL of the icon.
+    pub src: String,
+    /// The sizes of the icon, such as `192x192`.
+    pub sizes: String,
+    /// The MIME type of the icon, such as `image/png`.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    /// How the icon may be used, such as `any` or `maskable`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+}
+
+/// A web app manifest, describing how the app is installed.
+///
+/// # Example
+/// ```
+/// use next_rs::manifest::WebManifest;
+///
+/// let manifest = WebManifest::new("Acme Dashboard")
+///     .short_name("Acme")
+///     .theme_color("#0f172a")
+///     .icon("/icons/icon-192.png", "192x192", "image/png")
+///     .icon("/icons/icon-512.png", "512x512", "image/png");
+///
+/// let json = manifest.to_json().unwrap();
+/// assert!(json.contains("\"short_name\": \"Acme\""));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WebManifest {
+    /// The name of the app.
+    pub name: String,
+    /// The name of the app where space is limited, such as on a home screen.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub short_name: Option<String>,
+    /// The description of the app.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The URL the app opens at.
+    pub start_url: String,
+    /// The URLs belonging to the app.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// How the app is displayed, such as `standalone` or `fullscreen`.
+    pub display: String,
+    /// The preferred orientation, such as `portrait`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orientation: Option<String>,
+    /// The background colour of the splash screen.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<String>,
+    /// The colour of the browser UI.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme_color: Option<String>,
+    /// The language of the app, such as `en`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    /// The icons of the app.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub icons: Vec<ManifestIcon>,
+}
+
+impl Default for WebManifest {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            short_name: None,
+            description: None,
+            start_url: "/".to_string(),
+            scope: None,
+            display: "standalone".to_string(),
+            orientation: None,
+            background_color: None,
+            theme_color: None,
+            lang: None,
+            icons: Vec::new(),
+        }
+    }
+}
+
+impl WebManifest {
+    /// Creates a manifest for a standalone app starting at `/`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the short name of the app.
+    pub fn short_name(mut self, short_name: impl Into<String>) -> Self {
+        self.short_name = Some(short_name.into());
+        self
+    }
+
+    /// Sets the description of the app.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the URL the app opens at.
+    pub fn start_url(mut self, start_url: impl Into<String>) -> Self {
+        self.start_url = start_url.into();
+        self
+    }
+
+    /// Sets the URLs belonging to the app.
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    /// Sets how the app is displayed.
+    pub fn display(mut self, display: impl Into<String>) -> Self {
+        self.display = display.into();
+        self
+    }
+
+    /// Sets the preferred orientation.
+    pub fn orientation(mut self, orientation: impl Into<String>) -> Self {
+        self.orientation = Some(orientation.into());
+        self
+    }
+
+    /// Sets the background colour of the splash screen.
+    pub fn background_color(mut self, background_color: impl Into<String>) -> Self {
+        self.background_color = Some(background_color.into());
+        self
+    }
+
+    /// Sets the colour of the browser UI.
+    pub fn theme_color(mut self, theme_color: impl Into<String>) -> Self {
+        self.theme_color = Some(theme_color.into());
+        self
+    }
+
+    /// Sets the language of the app.
+    pub fn lang(mut self, lang: impl Into<String>) -> Self {
+        self.lang = Some(lang.into());
+        self
+    }
+
+    /// Adds an icon to the app.
+    pub fn icon(
+        mut self,
+        src: impl Into<String>,
+        sizes: impl Into<String>,
+        media_type: impl Into<String>,
+    ) -> Self {
+        self.icons.push(ManifestIcon {
+            src: src.into(),
+            sizes: sizes.into(),
+            media_type: Some(media_type.into()),
+            purpose: None,
+        });
+        self
+    }
+
+    /// Adds a maskable icon, which platforms may crop to their own shape.
+    pub fn maskable_icon(mut self, src: impl Into<String>, sizes: impl Into<String>) -> Self {
+        self.icons.push(ManifestIcon {
+            src: src.into(),
+            sizes: sizes.into(),
+            media_type: None,
+            purpose: Some("maskable".to_string()),
+        });
+        self
+    }
+
+    /// Serializes the manifest to pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Writes the manifest as `manifest.webmanifest` into `dir`, such as the dist directory, from
+    /// a build script or a native build step.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the path of the written file on success.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn write_to(
+        &self,
+        dir: impl AsRef<std::path::Path>,
+    ) -> std::io::Result<std::path::PathBuf> {
+        let json = self
+            .to_json()
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        std::fs::create_dir_all(dir.as_ref())?;
+        let path = dir.as_ref().join("manifest.webmanifest");
+        std::fs::write(&path, json)?;
+        Ok(path)
+    }
+}
+
+/// The favicons and touch icons of a site.
+///
+/// # Example
+/// ```rust
+/// use next_rs::prelude::*;
+/// use next_rs::manifest::{icons, IconSet};
+///
+/// #[func]
+/// pub fn App() -> Html {
+///     let icon_set = IconSet::new()
+///         .favicon("/favicon.ico")
+///         .svg("/icon.svg")
+///         .png(32, "/icon-32.png")
+///         .apple_touch_icon("/apple-touch-icon.png")
+///         .mask_icon("/safari-pinned-tab.svg", "#0f172a")
+///         .manifest("/manifest.webmanifest");
+///
+///     rsx! {
+///         <>{icons(&icon_set)}</>
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IconSet {
+    /// The URL of the `.ico` favicon.
+    pub favicon: Option<AttrValue>,
+    /// The URL of the SVG icon.
+    pub svg: Option<AttrValue>,
+    /// The URLs of the PNG icons, by size in pixels.
+    pub png: Vec<(u32, AttrValue)>,
+    /// The URL of the 180x180 Apple touch icon.
+    pub apple_touch_icon: Option<AttrValue>,
+    /// The URL and colour of the Safari pinned tab icon.
+    pub mask_icon: Option<(AttrValue, AttrValue)>,
+    /// The URL of the web app manifest.
+    pub manifest: Option<AttrValue>,
+}
+
+impl IconSet {
+    /// Creates an empty icon set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the URL of the `.ico` favicon.
+    pub fn favicon(mut self, href: impl Into<AttrValue>) -> Self {
+        self.favicon = Some(href.into());
+        self
+    }
+
+    /// Sets the URL of the SVG icon.
+    pub fn svg(mut self, href: impl Into<AttrValue>) -> Self {
+        self.svg = Some(href.into());
+        self
+    }
+
+    /// Adds a square PNG icon of the given size.
+    pub fn png(mut self, size: u32, href: impl Into<AttrValue>) -> Self {
+        self.png.push((size, href.into()));
+        self
+    }
+
+    /// Sets the URL of the Apple touch icon.
+    pub fn apple_touch_icon(mut self, href: impl Into<AttrValue>) -> Self {
+        self.apple_touch_icon = Some(href.into());
+        self
+    }
+
+    /// Sets the URL and colour of the Safari pinned tab icon.
+    pub fn mask_icon(mut self, href: impl Into<AttrValue>, color: impl Into<AttrValue>) -> Self {
+        self.mask_icon = Some((href.into(), color.into()));
+        self
+    }
+
+    /// Sets the URL of the web app manifest.
+    pub fn manifest(mut self, href: impl Into<AttrValue>) -> Self {
+        self.manifest = Some(href.into());
+        self
+    }
+
+    /// Returns the icons of the set, with their `rel`, sizes and type.
+    pub fn to_icons(&self) -> Vec<Icon> {
+        let mut icons = Vec::new();
+        if let Some(href) = &self.favicon {
+            icons.push(Icon {
+                sizes: Some("any".into()),
+                ..Icon::new(href.clone())
+            });
+        }
+        if let Some(href) = &self.svg {
+            icons.push(Icon {
+                media_type: Some("image/svg+xml".into()),
+                ..Icon::new(href.clone())
+            });
+        }
+        for (size, href) in &self.png {
+            icons.push(Icon {
+                sizes: Some(format!("{}x{}", size, size).into()),
+                media_type: Some("image/png".into()),
+                ..Icon::new(href.clone())
+            });
+        }
+        if let Some(href) = &self.apple_touch_icon {
+            icons.push(Icon {
+                rel: "apple-touch-icon".into(),
+                sizes: Some("180x180".into()),
+                ..Icon::new(href.clone())
+            });
+        }
+        if let Some((href, color)) = &self.mask_icon {
+            icons.push(Icon {
+                rel: "mask-icon".into(),
+                color: Some(color.clone()),
+                ..Icon::new(href.clone())
+            });
+        }
+        icons
+    }
+}
+
+/// Renders the `<link rel="icon|apple-touch-icon|mask-icon|manifest">` tags of an icon set
+/// through `Head`.
+pub fn icons(set: &IconSet) -> Html {
+    rsx! {
+        <Head>{icon_links(set)}</Head>
+    }
+}
+
+/// Renders the `<link>` tags of an icon set.
+fn icon_links(set: &IconSet) -> Html {
+    let meta = Metadata {
+        icons: set.to_icons(),
+        manifest: set.manifest.clone(),
+        ..Metadata::default()
+    };
+    meta.to_html()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::head::map_components;
+    use crate::testing::{snapshot, HeadEntry};
+    use serde_json::json;
+
+    #[test]
+    fn manifest_json_uses_the_spec_field_names() {
+        let manifest = WebManifest::new("Acme Dashboard")
+            .short_name("Acme")
+            .background_color("#ffffff")
+            .theme_color("#0f172a")
+            .icon("/icons/icon-192.png", "192x192", "image/png")
+            .maskable_icon("/icons/maskable-512.png", "512x512");
+        let json: serde_json::Value = serde_json::from_str(&manifest.to_json().unwrap()).unwrap();
+
+        assert_eq!(
+            json,
+            json!({
+                "name": "Acme Dashboard",
+                "short_name": "Acme",
+                "start_url": "/",
+                "display": "standalone",
+                "background_color": "#ffffff",
+                "theme_color": "#0f172a",
+                "icons": [
+                    { "src": "/icons/icon-192.png", "sizes": "192x192", "type": "image/png" },
+                    { "src": "/icons/maskable-512.png", "sizes": "512x512", "purpose": "maskable" },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn manifest_json_omits_unset_fields() {
+        let json: serde_json::Value =
+            serde_json::from_str(&WebManifest::new("Acme").to_json().unwrap()).unwrap();
+
+        assert_eq!(
+            json,
+            json!({ "name": "Acme", "start_url": "/", "display": "standalone" })
+        );
+    }
+
+    #[test]
+    fn icon_set_renders_every_link() {
+        let set = IconSet::new()
+            .favicon("/favicon.ico")
+            .svg("/icon.svg")
+            .png(32, "/icon-32.png")
+            .apple_touch_icon("/apple-touch-icon.png")
+            .mask_icon("/safari-pinned-tab.svg", "#0f172a")
+            .manifest("/manifest.webmanifest");
+        let head = map_components(vec![icon_links(&set)]);
+
+        assert_eq!(
+            snapshot(head.iter().filter_map(HeadEntry::from_html)),
+            "<link color=\"#0f172a\" href=\"/safari-pinned-tab.svg\" rel=\"mask-icon\">\n\
+             <link href=\"/apple-touch-icon.png\" rel=\"apple-touch-icon\" sizes=\"180x180\">\n\
+             <link href=\"/favicon.ico\" rel=\"icon\" sizes=\"any\">\n\
+             <link href=\"/icon-32.png\" rel=\"icon\" sizes=\"32x32\" type=\"image/png\">\n\
+             <link href=\"/icon.svg\" rel=\"icon\" type=\"image/svg+xml\">\n\
+             <link href=\"/manifest.webmanifest\" rel=\"manifest\">\n\
+             <meta charset=\"utf-8\">"
+        );
+    }
+}
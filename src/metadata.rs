@@ -0,0 +1,302 @@
+use crate::head::{merge_metadata, use_default_metadata, use_head};
+use crate::open_graph::{OpenGraph, TwitterCard};
+use crate::prelude::*;
+use crate::router::RouterContext;
+use crate::use_context;
+
+/// A hook merging the metadata of a page into the site defaults, with the basename of the router
+/// unless one is set.
+#[hook]
+fn use_merged_metadata(meta: &Metadata) -> Metadata {
+    let defaults = use_default_metadata();
+    let router = use_context::<RouterContext>();
+
+    let mut meta = merge_metadata(&defaults, meta);
+    if meta.basename.is_none() {
+        meta.basename = router
+            .map(|context| context.router().basename())
+            .filter(|basename| !basename.is_empty())
+            .map(AttrValue::from);
+    }
+    meta
+}
+
+/// An icon of the page, rendered as a `<link>` tag.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Icon {
+    /// The relationship of the icon, such as `icon` or `apple-touch-icon`.
+    pub rel: AttrValue,
+    /// The URL of the icon.
+    pub href: AttrValue,
+    /// The sizes of the icon, such as `32x32`.
+    pub sizes: Option<AttrValue>,
+    /// The MIME type of the icon, such as `image/png`.
+    pub media_type: Option<AttrValue>,
+    /// The colour of a `mask-icon`.
+    pub color: Option<AttrValue>,
+}
+
+impl Icon {
+    /// Creates an icon with the `icon` relationship.
+    pub fn new(href: impl Into<AttrValue>) -> Self {
+        Self {
+            rel: "icon".into(),
+            href: href.into(),
+            ..Self::default()
+        }
+    }
+}
+
+/// An alternate version of the page, rendered as a `<link rel="alternate">` tag.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Alternate {
+    /// The URL of the alternate version.
+    pub href: AttrValue,
+    /// The language of the alternate version, such as `fr` or `x-default`.
+    pub hreflang: Option<AttrValue>,
+    /// The MIME type of the alternate version, such as `application/rss+xml`.
+    pub media_type: Option<AttrValue>,
+    /// The title of the alternate version.
+    pub title: Option<AttrValue>,
+}
+
+/// Typed metadata of a page, rendered into `<head>` through the head manager.
+///
+/// Each field maps to the right tag and attribute names, so pages don't have to write raw
+/// `<meta>` and `<link>` tags. Empty fields render nothing.
+///
+/// # Example
+/// ```rust,no_run
+/// use next_rs::prelude::*;
+/// use next_rs::metadata::Metadata;
+///
+/// #[func]
+/// pub fn MyPage() -> Html {
+///     let meta = Metadata {
+///         title: Some("Pricing".into()),
+///         title_template: Some("%s | Acme".into()),
+///         description: Some("Plans for every team".into()),
+///         canonical: Some("https://acme.dev/pricing".into()),
+///         ..Metadata::default()
+///     };
+///
+///     rsx! {
+///         <next_rs::Metadata ..meta />
+///     }
+/// }
+/// ```
+#[derive(Properties, Debug, Clone, PartialEq, Default)]
+pub struct Metadata {
+    /// The title of the page.
+    #[prop_or_default]
+    pub title: Option<AttrValue>,
+
+    /// A template wrapping the title, where `%s` is replaced by the title, such as `%s | Acme`.
+    #[prop_or_default]
+    pub title_template: Option<AttrValue>,
+
+    /// The description of the page.
+    #[prop_or_default]
+    pub description: Option<AttrValue>,
+
+    /// The keywords of the page.
+    #[prop_or_default]
+    pub keywords: Vec<AttrValue>,
+
+    /// The authors of the page.
+    #[prop_or_default]
+    pub authors: Vec<AttrValue>,
+
+    /// The origin of the site, such as `https://acme.dev`, making relative canonical and alternate
+    /// URLs absolute.
+    #[prop_or_default]
+    pub origin: Option<AttrValue>,
+
+    /// The basename the site is served under, such as `/docs`, making relative canonical and
+    /// alternate URLs include it. Defaults to the basename of the router.
+    #[prop_or_default]
+    pub basename: Option<AttrValue>,
+
+    /// The canonical URL of the page, relative to the basename or absolute.
+    #[prop_or_default]
+    pub canonical: Option<AttrValue>,
+
+    /// The robots directives, such as `noindex, nofollow`, or a [`Robots`](crate::robots::Robots)
+    /// converted with `into()`.
+    #[prop_or_default]
+    pub robots: Option<AttrValue>,
+
+    /// The viewport settings, such as `width=device-width, initial-scale=1`.
+    #[prop_or_default]
+    pub viewport: Option<AttrValue>,
+
+    /// The theme colour used by the browser UI.
+    #[prop_or_default]
+    pub theme_color: Option<AttrValue>,
+
+    /// The icons of the page.
+    #[prop_or_default]
+    pub icons: Vec<Icon>,
+
+    /// The URL of the web app manifest.
+    #[prop_or_default]
+    pub manifest: Option<AttrValue>,
+
+    /// Alternate versions of the page, such as translations or feeds.
+    #[prop_or_default]
+    pub alternates: Vec<Alternate>,
+
+    /// The Open Graph object of the page. Its title, description and URL default to those of the
+    /// page.
+    #[prop_or_default]
+    pub open_graph: Option<OpenGraph>,
+
+    /// The Twitter card of the page.
+    #[prop_or_default]
+    pub twitter: Option<TwitterCard>,
+}
+
+impl Metadata {
+    /// Returns the title with the title template applied, if any.
+    pub fn full_title(&self) -> Option<AttrValue> {
+        let title = self.title.as_ref()?;
+        match &self.title_template {
+            Some(template) => Some(template.replace("%s", title.as_str()).into()),
+            None => Some(title.clone()),
+        }
+    }
+
+    /// Makes a URL relative to the basename absolute with the `basename` and `origin` of the site,
+    /// if any.
+    ///
+    /// # Example
+    /// ```
+    /// use next_rs::metadata::Metadata;
+    ///
+    /// let meta = Metadata {
+    ///     origin: Some("https://acme.dev".into()),
+    ///     basename: Some("/docs".into()),
+    ///     ..Metadata::default()
+    /// };
+    /// assert_eq!(meta.absolute_url(&"/pricing".into()), "https://acme.dev/docs/pricing");
+    /// assert_eq!(meta.absolute_url(&"https://x.dev/a".into()), "https://x.dev/a");
+    /// ```
+    pub fn absolute_url(&self, url: &AttrValue) -> AttrValue {
+        if !url.starts_with('/') || url.starts_with("//") {
+            return url.clone();
+        }
+        let trim = |value: &Option<AttrValue>| {
+            value
+                .as_deref()
+                .unwrap_or_default()
+                .trim_end_matches('/')
+                .to_string()
+        };
+        format!("{}{}{}", trim(&self.origin), trim(&self.basename), url).into()
+    }
+
+    /// Renders the metadata as head elements.
+    pub fn to_html(&self) -> Html {
+        let canonical = self.canonical.as_ref().map(|url| self.absolute_url(url));
+        let meta = |name: &'static str, content: &Option<AttrValue>| {
+            content.as_ref().map(|content| {
+                rsx! { <meta name={name} content={content.clone()} /> }
+            })
+        };
+        let link = |rel: &'static str, href: &Option<AttrValue>| {
+            href.as_ref().map(|href| {
+                rsx! { <link rel={rel} href={href.clone()} /> }
+            })
+        };
+        let open_graph = self.open_graph.clone().map(|open_graph| OpenGraph {
+            title: open_graph.title.or_else(|| self.full_title()),
+            description: open_graph.description.or_else(|| self.description.clone()),
+            url: open_graph.url.or_else(|| canonical.clone()),
+            ..open_graph
+        });
+        let keywords = join(&self.keywords);
+        let authors = join(&self.authors);
+
+        rsx! {
+            <>
+                {for self.full_title().map(|title| rsx! { <title>{title}</title> })}
+                {for meta("description", &self.description)}
+                {for meta("keywords", &keywords)}
+                {for meta("author", &authors)}
+                {for meta("robots", &self.robots)}
+                {for meta("viewport", &self.viewport)}
+                {for meta("theme-color", &self.theme_color)}
+                {for link("canonical", &canonical)}
+                {for self.icons.iter().map(|icon| rsx! {
+                    <link
+                        rel={icon.rel.clone()}
+                        href={icon.href.clone()}
+                        sizes={icon.sizes.clone()}
+                        type={icon.media_type.clone()}
+                        color={icon.color.clone()}
+                    />
+                })}
+                {for link("manifest", &self.manifest)}
+                {for self.alternates.iter().map(|alternate| rsx! {
+                    <link
+                        rel="alternate"
+                        href={self.absolute_url(&alternate.href)}
+                        hreflang={alternate.hreflang.clone()}
+                        type={alternate.media_type.clone()}
+                        title={alternate.title.clone()}
+                    />
+                })}
+                {for open_graph.map(|open_graph| open_graph.to_html())}
+                {for self.twitter.as_ref().map(|twitter| twitter.to_html())}
+            </>
+        }
+    }
+}
+
+/// Joins a list of values into a single comma-separated attribute value.
+fn join(values: &[AttrValue]) -> Option<AttrValue> {
+    let values: Vec<&str> = values.iter().map(|value| value.as_str()).collect();
+    (!values.is_empty()).then(|| values.join(", ").into())
+}
+
+/// A hook rendering typed metadata into `<head>`.
+///
+/// The metadata is merged into the site defaults of the nearest [`crate::head::MetadataProvider`]
+/// per [`merge_metadata`], and registered by the returned node like with [`use_head`]: the calling
+/// component must render it, and a deeper `Head` or `use_metadata` wins over it. The tags are
+/// removed when the component unmounts.
+///
+/// # Arguments
+///
+/// * `meta`: The metadata of the page.
+///
+/// # Returns
+///
+/// (Html): The node rendering the metadata.
+///
+/// # Example
+/// ```rust,no_run
+/// use next_rs::prelude::*;
+/// use next_rs::metadata::{use_metadata, Metadata};
+///
+/// #[func]
+/// pub fn MyPage() -> Html {
+///     let metadata = use_metadata(Metadata {
+///         title: Some("About".into()),
+///         ..Metadata::default()
+///     });
+///
+///     rsx! {
+///         <>
+///             {metadata}
+///             <h1>{"About"}</h1>
+///         </>
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_metadata(meta: Metadata) -> Html {
+    let meta = use_merged_metadata(&meta);
+
+    use_head(meta.to_html())
+}
@@ -0,0 +1,983 @@
+use crate::open_graph::OgMedia;
+use crate::prelude::*;
+use crate::sitemap::escape_xml;
+
+/// The default width of an Open Graph image, in pixels.
+pub const WIDTH: u32 = 1200;
+
+/// The default height of an Open Graph image, in pixels.
+pub const HEIGHT: u32 = 630;
+
+/// A font available to the text of an [`OgImage`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OgFont {
+    /// The family name of the font, as used by `font-family` and as stored in the font file.
+    pub family: String,
+    /// The weight of the font, such as `400` or `700`.
+    pub weight: u16,
+    /// The content of the TTF, OTF, WOFF or WOFF2 file.
+    pub data: Vec<u8>,
+}
+
+impl OgFont {
+    /// Creates a regular font from the content of its file.
+    pub fn new(family: impl Into<String>, data: Vec<u8>) -> Self {
+        Self {
+            family: family.into(),
+            weight: 400,
+            data,
+        }
+    }
+
+    /// Sets the weight of the font.
+    pub fn weight(mut self, weight: u16) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Returns the MIME type of the font, from the signature of its file.
+    fn media_type(&self) -> &'static str {
+        match self.data.get(..4) {
+            Some(b"wOF2") => "font/woff2",
+            Some(b"wOFF") => "font/woff",
+            Some(b"OTTO") => "font/otf",
+            _ => "font/ttf",
+        }
+    }
+}
+
+/// An Open Graph image described with a subset of `rsx!`, rendered to SVG, and to PNG in a
+/// native build step with the `og-png` feature.
+///
+/// Every element is a flex container, laid out in a row unless its `flex-direction` is `column`.
+/// The supported elements are:
+/// - text, wrapped to the width of its container;
+/// - boxes, any element other than `img`, such as `div`, `span`, `h1` or `p`;
+/// - images, `img` elements with a `src`, a `width` and a `height`.
+///
+/// The supported `style` properties are `width`, `height`, `padding`, `background` (a color),
+/// `border-radius`, `flex-direction`, `justify-content`, `align-items`, `gap`, `flex-grow`,
+/// `color`, `font-family`, `font-size`, `font-weight`, `line-height` and `text-align`. Lengths are
+/// in pixels, or in percents of the container for `width` and `height`. Text is measured from the
+/// glyphs of the fonts when rasterizing, and from average glyph widths in [`OgImage::to_svg`], so
+/// leave some room around it there.
+///
+/// # Example
+/// ```
+/// use next_rs::prelude::*;
+/// use next_rs::og_image::OgImage;
+///
+/// let image = OgImage::new(rsx! {
+///     <div style="flex-direction: column; justify-content: center; padding: 80px; \
+///                 background: #0f172a; color: #f8fafc">
+///         <h1 style="font-size: 72px; font-weight: 700">{"Hello, world"}</h1>
+///         <p style="font-size: 32px; color: #94a3b8">{"acme.dev/blog"}</p>
+///     </div>
+/// });
+///
+/// let svg = image.to_svg();
+/// assert!(svg.starts_with("<svg"));
+/// assert!(svg.contains(">Hello, world</text>"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct OgImage {
+    /// The width of the image, in pixels.
+    pub width: u32,
+    /// The height of the image, in pixels.
+    pub height: u32,
+    /// The fonts of the text.
+    pub fonts: Vec<OgFont>,
+    /// The directory relative image URLs are resolved from when rasterizing.
+    pub assets_dir: Option<std::path::PathBuf>,
+    /// The content of the image.
+    pub content: Html,
+}
+
+impl OgImage {
+    /// Creates a 1200x630 image from its content.
+    pub fn new(content: Html) -> Self {
+        Self {
+            width: WIDTH,
+            height: HEIGHT,
+            fonts: Vec::new(),
+            assets_dir: None,
+            content,
+        }
+    }
+
+    /// Sets the size of the image, in pixels.
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Adds a font for the text of the image.
+    pub fn font(mut self, font: OgFont) -> Self {
+        self.fonts.push(font);
+        self
+    }
+
+    /// Sets the directory relative image URLs are resolved from when rasterizing.
+    pub fn assets_dir(mut self, dir: impl Into<std::path::PathBuf>) -> Self {
+        self.assets_dir = Some(dir.into());
+        self
+    }
+
+    /// Renders the image to SVG.
+    pub fn to_svg(&self) -> String {
+        self.render_svg(&estimate_width)
+    }
+
+    /// Renders the image to SVG, measuring text with `text_width`.
+    fn render_svg(&self, text_width: TextWidth) -> String {
+        let (width, height) = (self.width as f32, self.height as f32);
+        let mut root_style = Style::root();
+        root_style.width = Some(Length::Px(width));
+        root_style.height = Some(Length::Px(height));
+        root_style.column = true;
+
+        let mut children = Vec::new();
+        parse(&self.content, &root_style, &mut children);
+        let root = Node::Box {
+            style: root_style,
+            children,
+        };
+
+        let mut svg = Svg {
+            body: String::new(),
+            clip_paths: 0,
+            text_width,
+        };
+        render(&root, 0.0, 0.0, width, height, &mut svg);
+
+        let fonts: String = self
+            .fonts
+            .iter()
+            .map(|font| {
+                format!(
+                    "@font-face {{ font-family: \"{}\"; font-weight: {}; \
+                     src: url(data:{};base64,{}); }}\n",
+                    escape_xml(&font.family),
+                    font.weight,
+                    font.media_type(),
+                    base64(&font.data)
+                )
+            })
+            .collect();
+        let style = match fonts.is_empty() {
+            true => String::new(),
+            false => format!("<style>\n{}</style>\n", fonts),
+        };
+
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+             viewBox=\"0 0 {w} {h}\">\n{}{}</svg>\n",
+            style,
+            svg.body,
+            w = self.width,
+            h = self.height
+        )
+    }
+
+    /// Rasterizes the image to PNG.
+    ///
+    /// The fonts of the image are used, along with the fonts installed on the system, to measure
+    /// and draw the text.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the PNG file on success and an [`OgImageError`] otherwise.
+    #[cfg(all(feature = "og-png", not(target_arch = "wasm32")))]
+    pub fn to_png(&self) -> Result<Vec<u8>, OgImageError> {
+        use resvg::{tiny_skia, usvg};
+
+        let mut fontdb = usvg::fontdb::Database::new();
+        fontdb.load_system_fonts();
+        for font in &self.fonts {
+            fontdb.load_font_data(font.data.clone());
+        }
+        let options = usvg::Options {
+            resources_dir: self.assets_dir.clone(),
+            ..usvg::Options::default()
+        };
+        let text_width = |text: &str, style: &Style| {
+            font_width(&fontdb, text, style).unwrap_or_else(|| estimate_width(text, style))
+        };
+        let svg = self.render_svg(&text_width);
+        let tree = usvg::Tree::from_str(&svg, &options, &fontdb)
+            .map_err(|err| OgImageError::Svg(err.to_string()))?;
+
+        let mut pixmap = tiny_skia::Pixmap::new(self.width, self.height)
+            .ok_or_else(|| OgImageError::Render("the image is empty".to_string()))?;
+        resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+        pixmap
+            .encode_png()
+            .map_err(|err| OgImageError::Render(err.to_string()))
+    }
+
+    /// Writes the PNG image of a route into `dir`, such as the dist directory, at the path of
+    /// [`image_path`], from a build script or a native build step.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the path of the written file on success.
+    #[cfg(all(feature = "og-png", not(target_arch = "wasm32")))]
+    pub fn write_png(
+        &self,
+        dir: impl AsRef<std::path::Path>,
+        route: &str,
+    ) -> std::io::Result<std::path::PathBuf> {
+        let png = self
+            .to_png()
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        let path = dir.as_ref().join(image_path(route).trim_start_matches('/'));
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, png)?;
+        Ok(path)
+    }
+}
+
+/// Represents errors raised while rasterizing an [`OgImage`].
+#[cfg(all(feature = "og-png", not(target_arch = "wasm32")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OgImageError {
+    /// The rendered SVG could not be parsed.
+    Svg(String),
+    /// The SVG could not be rasterized or encoded.
+    Render(String),
+}
+
+#[cfg(all(feature = "og-png", not(target_arch = "wasm32")))]
+impl std::fmt::Display for OgImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OgImageError::Svg(err) => write!(f, "invalid SVG: {}", err),
+            OgImageError::Render(err) => write!(f, "failed to render: {}", err),
+        }
+    }
+}
+
+#[cfg(all(feature = "og-png", not(target_arch = "wasm32")))]
+impl std::error::Error for OgImageError {}
+
+/// Returns the path of the Open Graph image of a route, such as `/og/blog/hello.png` for
+/// `/blog/hello`.
+///
+/// # Example
+/// ```
+/// use next_rs::og_image::image_path;
+///
+/// assert_eq!(image_path("/blog/hello"), "/og/blog/hello.png");
+/// assert_eq!(image_path("/"), "/og/index.png");
+/// ```
+pub fn image_path(route: &str) -> String {
+    let route = route.split(['?', '#']).next().unwrap_or_default();
+    match route.trim_matches('/') {
+        "" => "/og/index.png".to_string(),
+        route => format!("/og/{}.png", route),
+    }
+}
+
+/// Returns the Open Graph image of a route, as written by [`OgImage::write_png`], for the
+/// Open Graph metadata of the page.
+///
+/// The image is expected to have the default size, which can be changed with [`OgMedia::size`].
+///
+/// # Example
+/// ```rust
+/// use next_rs::prelude::*;
+/// use next_rs::metadata::Metadata;
+/// use next_rs::og_image;
+/// use next_rs::open_graph::OpenGraph;
+///
+/// #[func]
+/// pub fn Post() -> Html {
+///     let image = og_image::media("https://acme.dev", "/blog/hello").alt("Hello, world");
+///     let meta = Metadata {
+///         title: Some("Hello, world".into()),
+///         open_graph: Some(OpenGraph::website().image(image)),
+///         ..Metadata::default()
+///     };
+///
+///     rsx! {
+///         <next_rs::Metadata ..meta />
+///     }
+/// }
+/// ```
+pub fn media(origin: &str, route: &str) -> OgMedia {
+    let url = format!("{}{}", origin.trim_end_matches('/'), image_path(route));
+    OgMedia::new(url)
+        .media_type("image/png")
+        .size(WIDTH, HEIGHT)
+}
+
+/// A length of the `style` attribute.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Length {
+    Px(f32),
+    Percent(f32),
+}
+
+impl Length {
+    /// Parses a length in pixels or percents.
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().strip_suffix('%') {
+            Some(percent) => percent.trim().parse().ok().map(Length::Percent),
+            None => px(value).map(Length::Px),
+        }
+    }
+
+    /// Returns the length in pixels, relative to `base` for percents.
+    fn resolve(self, base: f32) -> f32 {
+        match self {
+            Length::Px(px) => px,
+            Length::Percent(percent) => base * percent / 100.0,
+        }
+    }
+}
+
+/// Parses a length in pixels, with or without its unit.
+fn px(value: &str) -> Option<f32> {
+    let value = value.trim();
+    value
+        .strip_suffix("px")
+        .unwrap_or(value)
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// The style of a node, from its `style` attribute and those of its ancestors.
+#[derive(Debug, Clone, PartialEq)]
+struct Style {
+    width: Option<Length>,
+    height: Option<Length>,
+    /// Top, right, bottom and left padding.
+    padding: [f32; 4],
+    background: Option<String>,
+    border_radius: f32,
+    column: bool,
+    justify: String,
+    align: String,
+    gap: f32,
+    grow: f32,
+    // Inherited properties.
+    color: String,
+    font_family: String,
+    font_size: f32,
+    font_weight: u16,
+    line_height: f32,
+    text_align: String,
+}
+
+impl Style {
+    /// Returns the style of the root of the image.
+    fn root() -> Self {
+        Self {
+            width: None,
+            height: None,
+            padding: [0.0; 4],
+            background: None,
+            border_radius: 0.0,
+            column: false,
+            justify: "flex-start".to_string(),
+            align: "stretch".to_string(),
+            gap: 0.0,
+            grow: 0.0,
+            color: "#000000".to_string(),
+            font_family: "sans-serif".to_string(),
+            font_size: 16.0,
+            font_weight: 400,
+            line_height: 1.2,
+            text_align: "left".to_string(),
+        }
+    }
+
+    /// Returns the style a child starts from, keeping the inherited properties.
+    fn child(&self) -> Self {
+        Self {
+            color: self.color.clone(),
+            font_family: self.font_family.clone(),
+            font_size: self.font_size,
+            font_weight: self.font_weight,
+            line_height: self.line_height,
+            text_align: self.text_align.clone(),
+            ..Self::root()
+        }
+    }
+
+    /// Applies the declarations of a `style` attribute, ignoring unsupported ones.
+    fn apply(&mut self, css: &str) {
+        for declaration in css.split(';') {
+            let (property, value) = match declaration.split_once(':') {
+                Some((property, value)) => (property.trim(), value.trim()),
+                None => continue,
+            };
+            match property {
+                "width" => self.width = Length::parse(value),
+                "height" => self.height = Length::parse(value),
+                "padding" => {
+                    let values: Vec<f32> = value.split_whitespace().filter_map(px).collect();
+                    self.padding = match values[..] {
+                        [all] => [all; 4],
+                        [vertical, horizontal] => [vertical, horizontal, vertical, horizontal],
+                        [top, horizontal, bottom] => [top, horizontal, bottom, horizontal],
+                        [top, right, bottom, left] => [top, right, bottom, left],
+                        _ => self.padding,
+                    };
+                }
+                "background" | "background-color" => self.background = Some(value.to_string()),
+                "border-radius" => self.border_radius = px(value).unwrap_or_default(),
+                "flex-direction" => self.column = value.starts_with("column"),
+                "justify-content" => self.justify = value.to_string(),
+                "align-items" => self.align = value.to_string(),
+                "gap" => self.gap = px(value).unwrap_or_default(),
+                "flex-grow" => self.grow = value.parse().unwrap_or_default(),
+                "color" => self.color = value.to_string(),
+                "font-family" => self.font_family = value.to_string(),
+                "font-size" => self.font_size = px(value).unwrap_or(self.font_size),
+                "font-weight" => {
+                    self.font_weight = match value {
+                        "bold" => 700,
+                        "normal" => 400,
+                        value => value.parse().unwrap_or(self.font_weight),
+                    }
+                }
+                "line-height" => self.line_height = value.parse().unwrap_or(self.line_height),
+                "text-align" => self.text_align = value.to_string(),
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the horizontal and vertical padding.
+    fn padding_size(&self) -> (f32, f32) {
+        (
+            self.padding[1] + self.padding[3],
+            self.padding[0] + self.padding[2],
+        )
+    }
+}
+
+/// A node of the image.
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Box { style: Style, children: Vec<Node> },
+    Text { style: Style, text: String },
+    Image { style: Style, src: String },
+}
+
+impl Node {
+    fn style(&self) -> &Style {
+        match self {
+            Node::Box { style, .. } | Node::Text { style, .. } | Node::Image { style, .. } => style,
+        }
+    }
+}
+
+/// Parses the supported subset of `html` into nodes. Components and other unsupported nodes are
+/// skipped.
+fn parse(html: &Html, parent: &Style, nodes: &mut Vec<Node>) {
+    match html {
+        Html::VList(list) => {
+            for child in list.iter() {
+                parse(child, parent, nodes);
+            }
+        }
+        Html::VText(text) => {
+            let text: Vec<&str> = text.text.split_whitespace().collect();
+            if !text.is_empty() {
+                nodes.push(Node::Text {
+                    style: parent.child(),
+                    text: text.join(" "),
+                });
+            }
+        }
+        Html::VTag(tag) => {
+            let attribute = |name: &str| {
+                tag.attributes
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            };
+            let mut style = parent.child();
+            if let Some(css) = attribute("style") {
+                style.apply(&css);
+            }
+
+            if tag.tag() == "img" {
+                style.width = style
+                    .width
+                    .or(attribute("width").and_then(|w| Length::parse(&w)));
+                style.height = style
+                    .height
+                    .or(attribute("height").and_then(|h| Length::parse(&h)));
+                nodes.push(Node::Image {
+                    style,
+                    src: attribute("src").unwrap_or_default(),
+                });
+                return;
+            }
+
+            let mut children = Vec::new();
+            if let Some(html) = tag.children() {
+                parse(html, &style, &mut children);
+            }
+            nodes.push(Node::Box { style, children });
+        }
+        _ => {}
+    }
+}
+
+/// Measures the width of a line of text in a style.
+type TextWidth<'a> = &'a dyn Fn(&str, &Style) -> f32;
+
+/// Returns the approximate width of text, from the average width of its glyphs.
+fn estimate_width(text: &str, style: &Style) -> f32 {
+    let ratio = if style.font_weight >= 600 { 0.6 } else { 0.55 };
+    text.chars().count() as f32 * style.font_size * ratio
+}
+
+/// Returns the width of text from the horizontal advances of the glyphs of the first font of
+/// `fontdb` matching its style, or `None` when no font matches.
+#[cfg(all(feature = "og-png", not(target_arch = "wasm32")))]
+fn font_width(fontdb: &resvg::usvg::fontdb::Database, text: &str, style: &Style) -> Option<f32> {
+    use resvg::usvg::fontdb::{Family, Query, Weight};
+
+    let names: Vec<&str> = style
+        .font_family
+        .split(',')
+        .map(|name| name.trim().trim_matches(|c| c == '"' || c == '\''))
+        .collect();
+    let mut families: Vec<Family> = names
+        .iter()
+        .map(|name| match *name {
+            "serif" => Family::Serif,
+            "sans-serif" => Family::SansSerif,
+            "monospace" => Family::Monospace,
+            "cursive" => Family::Cursive,
+            "fantasy" => Family::Fantasy,
+            name => Family::Name(name),
+        })
+        .collect();
+    families.push(Family::SansSerif);
+
+    let id = fontdb.query(&Query {
+        families: &families,
+        weight: Weight(style.font_weight),
+        ..Query::default()
+    })?;
+    fontdb.with_face_data(id, |data, index| {
+        let face = ttf_parser::Face::parse(data, index).ok()?;
+        let advance: f32 = text
+            .chars()
+            .filter_map(|c| face.glyph_hor_advance(face.glyph_index(c)?))
+            .map(f32::from)
+            .sum();
+        Some(advance * style.font_size / f32::from(face.units_per_em()))
+    })?
+}
+
+/// Wraps text into lines fitting `max_width`, returning each line with its width.
+fn wrap(text: &str, style: &Style, max_width: f32, text_width: TextWidth) -> Vec<(String, f32)> {
+    let mut lines: Vec<(String, f32)> = Vec::new();
+    for word in text.split_whitespace() {
+        if let Some((line, width)) = lines.last_mut() {
+            let joined = format!("{} {}", line, word);
+            let joined_width = text_width(&joined, style);
+            if joined_width <= max_width {
+                *line = joined;
+                *width = joined_width;
+                continue;
+            }
+        }
+        lines.push((word.to_string(), text_width(word, style)));
+    }
+    lines
+}
+
+/// Measures the size of a node in the space available to it.
+fn measure(
+    node: &Node,
+    available_width: f32,
+    available_height: f32,
+    text_width: TextWidth,
+) -> (f32, f32) {
+    match node {
+        Node::Text { style, text } => {
+            let lines = wrap(text, style, available_width, text_width);
+            let width = lines.iter().map(|(_, width)| *width).fold(0.0, f32::max);
+            let height = lines.len() as f32 * style.font_size * style.line_height;
+            (width, height)
+        }
+        Node::Image { style, .. } => (
+            style.width.map_or(0.0, |w| w.resolve(available_width)),
+            style.height.map_or(0.0, |h| h.resolve(available_height)),
+        ),
+        Node::Box { style, children } => {
+            let width = style.width.map(|w| w.resolve(available_width));
+            let height = style.height.map(|h| h.resolve(available_height));
+            let (padding_x, padding_y) = style.padding_size();
+            let inner_width = (width.unwrap_or(available_width) - padding_x).max(0.0);
+            let inner_height = (height.unwrap_or(available_height) - padding_y).max(0.0);
+
+            let sizes = measure_children(style, children, inner_width, inner_height, text_width);
+            let gaps = style.gap * sizes.len().saturating_sub(1) as f32;
+            let (content_width, content_height) = match style.column {
+                true => (
+                    sizes.iter().map(|(w, _)| *w).fold(0.0, f32::max),
+                    sizes.iter().map(|(_, h)| *h).sum::<f32>() + gaps,
+                ),
+                false => (
+                    sizes.iter().map(|(w, _)| *w).sum::<f32>() + gaps,
+                    sizes.iter().map(|(_, h)| *h).fold(0.0, f32::max),
+                ),
+            };
+
+            (
+                width.unwrap_or(content_width + padding_x),
+                height.unwrap_or(content_height + padding_y),
+            )
+        }
+    }
+}
+
+/// Measures the children of a box, a row sharing its width between them in order.
+fn measure_children(
+    style: &Style,
+    children: &[Node],
+    inner_width: f32,
+    inner_height: f32,
+    text_width: TextWidth,
+) -> Vec<(f32, f32)> {
+    let mut used = 0.0;
+    children
+        .iter()
+        .map(|child| match style.column {
+            true => measure(child, inner_width, inner_height, text_width),
+            false => {
+                let available_width = (inner_width - used).max(0.0);
+                let size = measure(child, available_width, inner_height, text_width);
+                used += size.0 + style.gap;
+                size
+            }
+        })
+        .collect()
+}
+
+/// The SVG elements of the image.
+struct Svg<'a> {
+    body: String,
+    clip_paths: usize,
+    text_width: TextWidth<'a>,
+}
+
+/// Lays out a node in the given box and renders it.
+fn render(node: &Node, x: f32, y: f32, width: f32, height: f32, svg: &mut Svg) {
+    match node {
+        Node::Text { style, text } => {
+            let line_height = style.font_size * style.line_height;
+            let (anchor, anchor_x) = match style.text_align.as_str() {
+                "center" => ("middle", x + width / 2.0),
+                "right" | "end" => ("end", x + width),
+                _ => ("start", x),
+            };
+            for (index, (line, _)) in wrap(text, style, width, svg.text_width).iter().enumerate() {
+                // The baseline sits about 80% down the em box, centered in the line.
+                let baseline = y
+                    + index as f32 * line_height
+                    + (line_height - style.font_size) / 2.0
+                    + style.font_size * 0.8;
+                svg.body.push_str(&format!(
+                    "<text x=\"{:.1}\" y=\"{:.1}\" font-family=\"{}\" font-size=\"{}\" \
+                     font-weight=\"{}\" fill=\"{}\" text-anchor=\"{}\">{}</text>\n",
+                    anchor_x,
+                    baseline,
+                    escape_xml(&style.font_family),
+                    style.font_size,
+                    style.font_weight,
+                    escape_xml(&style.color),
+                    anchor,
+                    escape_xml(line)
+                ));
+            }
+        }
+        Node::Image { style, src } => {
+            let clip = match style.border_radius > 0.0 {
+                true => {
+                    svg.clip_paths += 1;
+                    svg.body.push_str(&format!(
+                        "<clipPath id=\"clip-{}\"><rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" \
+                         height=\"{:.1}\" rx=\"{:.1}\" /></clipPath>\n",
+                        svg.clip_paths, x, y, width, height, style.border_radius
+                    ));
+                    format!(" clip-path=\"url(#clip-{})\"", svg.clip_paths)
+                }
+                false => String::new(),
+            };
+            svg.body.push_str(&format!(
+                "<image href=\"{}\" x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" \
+                 preserveAspectRatio=\"xMidYMid slice\"{} />\n",
+                escape_xml(src),
+                x,
+                y,
+                width,
+                height,
+                clip
+            ));
+        }
+        Node::Box { style, children } => {
+            if let Some(background) = &style.background {
+                svg.body.push_str(&format!(
+                    "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" rx=\"{:.1}\" \
+                     fill=\"{}\" />\n",
+                    x,
+                    y,
+                    width,
+                    height,
+                    style.border_radius,
+                    escape_xml(background)
+                ));
+            }
+            render_children(style, children, x, y, width, height, svg);
+        }
+    }
+}
+
+/// Lays out the children of a box along its main axis, then its cross axis, and renders them.
+fn render_children(
+    style: &Style,
+    children: &[Node],
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    svg: &mut Svg,
+) {
+    let (padding_x, padding_y) = style.padding_size();
+    let inner_x = x + style.padding[3];
+    let inner_y = y + style.padding[0];
+    let inner_width = (width - padding_x).max(0.0);
+    let inner_height = (height - padding_y).max(0.0);
+    let (inner_main, inner_cross) = match style.column {
+        true => (inner_height, inner_width),
+        false => (inner_width, inner_height),
+    };
+
+    // Sizes along the main and cross axes.
+    let mut sizes: Vec<(f32, f32)> =
+        measure_children(style, children, inner_width, inner_height, svg.text_width)
+            .into_iter()
+            .map(|(w, h)| match style.column {
+                true => (h, w),
+                false => (w, h),
+            })
+            .collect();
+
+    let gaps = style.gap * children.len().saturating_sub(1) as f32;
+    let mut free = inner_main - sizes.iter().map(|(main, _)| *main).sum::<f32>() - gaps;
+    let grow: f32 = children.iter().map(|child| child.style().grow).sum();
+    if free > 0.0 && grow > 0.0 {
+        for (size, child) in sizes.iter_mut().zip(children) {
+            size.0 += free * child.style().grow / grow;
+        }
+        free = 0.0;
+    }
+
+    let count = children.len() as f32;
+    let free = free.max(0.0);
+    let (mut offset, spacing) = match style.justify.as_str() {
+        "center" => (free / 2.0, 0.0),
+        "flex-end" | "end" => (free, 0.0),
+        "space-between" if count > 1.0 => (0.0, free / (count - 1.0)),
+        "space-around" if count > 0.0 => (free / count / 2.0, free / count),
+        "space-evenly" => (free / (count + 1.0), free / (count + 1.0)),
+        _ => (0.0, 0.0),
+    };
+
+    for ((main, cross), child) in sizes.into_iter().zip(children) {
+        let child_style = child.style();
+        let fixed_cross = match style.column {
+            true => child_style.width.is_some(),
+            false => child_style.height.is_some(),
+        };
+        let stretch =
+            style.align == "stretch" && !fixed_cross && !matches!(child, Node::Image { .. });
+        let cross = if stretch { inner_cross } else { cross };
+        let cross_offset = match style.align.as_str() {
+            "center" => (inner_cross - cross) / 2.0,
+            "flex-end" | "end" => inner_cross - cross,
+            _ => 0.0,
+        };
+
+        let (child_x, child_y, child_width, child_height) = match style.column {
+            true => (inner_x + cross_offset, inner_y + offset, cross, main),
+            false => (inner_x + offset, inner_y + cross_offset, main, cross),
+        };
+        render(child, child_x, child_y, child_width, child_height, svg);
+        offset += main + style.gap + spacing;
+    }
+}
+
+/// Encodes bytes in standard base64, with padding.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let triple = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for index in 0..4 {
+            match index <= chunk.len() {
+                true => encoded.push(ALPHABET[(triple >> (18 - 6 * index)) as usize & 63] as char),
+                false => encoded.push('='),
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn style(css: &str) -> Style {
+        let mut style = Style::root();
+        style.font_size = 10.0;
+        style.apply(css);
+        style
+    }
+
+    fn image(width: f32, height: f32) -> Node {
+        Node::Image {
+            style: style(&format!("width: {}px; height: {}px", width, height)),
+            src: "/logo.png".to_string(),
+        }
+    }
+
+    fn layout(content: Html) -> String {
+        OgImage::new(content).size(300, 100).to_svg()
+    }
+
+    #[test]
+    fn padding_shorthand_follows_css() {
+        assert_eq!(style("padding: 8px").padding, [8.0; 4]);
+        assert_eq!(
+            style("padding: 10px 20px").padding,
+            [10.0, 20.0, 10.0, 20.0]
+        );
+        assert_eq!(style("padding: 1px 2px 3px").padding, [1.0, 2.0, 3.0, 2.0]);
+        assert_eq!(
+            style("padding: 1px 2px 3px 4px").padding,
+            [1.0, 2.0, 3.0, 4.0]
+        );
+        assert_eq!(style("padding: 8px; padding: auto").padding, [8.0; 4]);
+    }
+
+    #[test]
+    fn text_wraps_to_the_available_width() {
+        let lines = wrap("aaa bbb ccc", &style(""), 40.0, &estimate_width);
+        assert_eq!(
+            lines,
+            vec![("aaa bbb".to_string(), 38.5), ("ccc".to_string(), 16.5)]
+        );
+
+        let lines = wrap("overflowing", &style(""), 10.0, &estimate_width);
+        assert_eq!(lines, vec![("overflowing".to_string(), 60.5)]);
+
+        let narrow = |text: &str, _: &Style| text.len() as f32;
+        let lines = wrap("aaa bbb ccc", &style(""), 40.0, &narrow);
+        assert_eq!(lines, vec![("aaa bbb ccc".to_string(), 11.0)]);
+    }
+
+    #[test]
+    fn boxes_are_measured_from_their_padding_gap_and_children() {
+        let node = Node::Box {
+            style: style("padding: 10px 20px; gap: 10px"),
+            children: vec![image(100.0, 50.0), image(100.0, 30.0)],
+        };
+        assert_eq!(
+            measure(&node, 1200.0, 630.0, &estimate_width),
+            (250.0, 70.0)
+        );
+
+        let node = Node::Box {
+            style: style("flex-direction: column; padding: 10px 20px; gap: 10px"),
+            children: vec![image(100.0, 50.0), image(80.0, 30.0)],
+        };
+        assert_eq!(
+            measure(&node, 1200.0, 630.0, &estimate_width),
+            (140.0, 110.0)
+        );
+
+        let node = Node::Box {
+            style: style("width: 50%; height: 200px; padding: 10px"),
+            children: vec![image(100.0, 50.0)],
+        };
+        assert_eq!(
+            measure(&node, 1200.0, 630.0, &estimate_width),
+            (600.0, 200.0)
+        );
+    }
+
+    #[test]
+    fn growing_children_share_the_free_space() {
+        let svg = layout(rsx! {
+            <div style="width: 300px; height: 100px">
+                <div style="width: 100px; background: red"></div>
+                <div style="flex-grow: 1; background: blue"></div>
+            </div>
+        });
+        assert!(svg.contains("<rect x=\"0.0\" y=\"0.0\" width=\"100.0\" height=\"100.0\""));
+        assert!(svg.contains("<rect x=\"100.0\" y=\"0.0\" width=\"200.0\" height=\"100.0\""));
+    }
+
+    #[test]
+    fn children_are_justified_along_the_main_axis() {
+        let svg = layout(rsx! {
+            <div style="width: 300px; height: 100px; justify-content: center">
+                <div style="width: 100px; background: red"></div>
+            </div>
+        });
+        assert!(svg.contains("<rect x=\"100.0\" y=\"0.0\" width=\"100.0\""));
+
+        let svg = layout(rsx! {
+            <div style="width: 300px; height: 100px; justify-content: space-between">
+                <div style="width: 50px; background: red"></div>
+                <div style="width: 50px; background: blue"></div>
+            </div>
+        });
+        assert!(svg.contains("<rect x=\"0.0\" y=\"0.0\" width=\"50.0\""));
+        assert!(svg.contains("<rect x=\"250.0\" y=\"0.0\" width=\"50.0\""));
+
+        let svg = layout(rsx! {
+            <div style="width: 300px; height: 100px; padding: 10px 20px; \
+                        flex-direction: column; align-items: center; justify-content: flex-end">
+                <div style="width: 100px; height: 20px; background: red"></div>
+            </div>
+        });
+        assert!(svg.contains("<rect x=\"100.0\" y=\"70.0\" width=\"100.0\" height=\"20.0\""));
+    }
+
+    #[test]
+    fn base64_pads_partial_chunks() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+        assert_eq!(base64(&[0xff, 0xfe]), "//4=");
+    }
+
+    #[test]
+    fn image_paths_ignore_slashes_queries_and_fragments() {
+        assert_eq!(image_path(""), "/og/index.png");
+        assert_eq!(image_path("/?page=2"), "/og/index.png");
+        assert_eq!(image_path("/blog/hello/"), "/og/blog/hello.png");
+        assert_eq!(image_path("/blog/hello?ref=feed#top"), "/og/blog/hello.png");
+    }
+}
@@ -0,0 +1,895 @@
+use crate::log;
+use crate::prelude::*;
+
+/// Renders a `<meta property content>` tag.
+fn property(property: &'static str, content: impl Into<AttrValue>) -> Html {
+    rsx! { <meta property={property} content={content.into()} /> }
+}
+
+/// Renders a `<meta name content>` tag.
+fn name(name: &'static str, content: impl Into<AttrValue>) -> Html {
+    rsx! { <meta name={name} content={content.into()} /> }
+}
+
+/// An image or a video attached to an Open Graph object.
+///
+/// # Example
+/// ```
+/// use next_rs::open_graph::OgMedia;
+///
+/// let image = OgMedia::new("https://acme.dev/og.png")
+///     .size(1200, 630)
+///     .alt("The Acme logo");
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OgMedia {
+    /// The URL of the media.
+    pub url: AttrValue,
+    /// The HTTPS URL of the media, if it differs from `url`.
+    pub secure_url: Option<AttrValue>,
+    /// The MIME type of the media, such as `image/png`.
+    pub media_type: Option<AttrValue>,
+    /// The width of the media, in pixels.
+    pub width: Option<u32>,
+    /// The height of the media, in pixels.
+    pub height: Option<u32>,
+    /// A description of the media, for people who can't see it.
+    pub alt: Option<AttrValue>,
+}
+
+impl OgMedia {
+    /// Creates a media from its URL.
+    pub fn new(url: impl Into<AttrValue>) -> Self {
+        Self {
+            url: url.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the HTTPS URL of the media.
+    pub fn secure_url(mut self, secure_url: impl Into<AttrValue>) -> Self {
+        self.secure_url = Some(secure_url.into());
+        self
+    }
+
+    /// Sets the MIME type of the media.
+    pub fn media_type(mut self, media_type: impl Into<AttrValue>) -> Self {
+        self.media_type = Some(media_type.into());
+        self
+    }
+
+    /// Sets the width and height of the media, in pixels.
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = Some(width);
+        self.height = Some(height);
+        self
+    }
+
+    /// Sets the description of the media.
+    pub fn alt(mut self, alt: impl Into<AttrValue>) -> Self {
+        self.alt = Some(alt.into());
+        self
+    }
+
+    /// Renders the media as the structured `og:image` or `og:video` properties.
+    fn to_html(&self, prefix: &'static str, tags: &mut Vec<Html>) {
+        let (secure_url, media_type, width, height, alt) = match prefix {
+            "og:video" => (
+                "og:video:secure_url",
+                "og:video:type",
+                "og:video:width",
+                "og:video:height",
+                "og:video:alt",
+            ),
+            _ => (
+                "og:image:secure_url",
+                "og:image:type",
+                "og:image:width",
+                "og:image:height",
+                "og:image:alt",
+            ),
+        };
+
+        tags.push(property(prefix, self.url.clone()));
+        tags.extend(self.secure_url.clone().map(|url| property(secure_url, url)));
+        tags.extend(
+            self.media_type
+                .clone()
+                .map(|kind| property(media_type, kind)),
+        );
+        tags.extend(self.width.map(|w| property(width, w.to_string())));
+        tags.extend(self.height.map(|h| property(height, h.to_string())));
+        tags.extend(self.alt.clone().map(|text| property(alt, text)));
+    }
+}
+
+/// The `article` properties of an Open Graph object.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Article {
+    /// When the article was first published, as an ISO 8601 date time.
+    pub published_time: Option<AttrValue>,
+    /// When the article was last changed, as an ISO 8601 date time.
+    pub modified_time: Option<AttrValue>,
+    /// When the article is out of date, as an ISO 8601 date time.
+    pub expiration_time: Option<AttrValue>,
+    /// The authors of the article, as profile URLs.
+    pub authors: Vec<AttrValue>,
+    /// The section of the article, such as `Technology`.
+    pub section: Option<AttrValue>,
+    /// The tags of the article.
+    pub tags: Vec<AttrValue>,
+}
+
+impl Article {
+    /// Creates empty article properties.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets when the article was first published.
+    pub fn published_time(mut self, time: impl Into<AttrValue>) -> Self {
+        self.published_time = Some(time.into());
+        self
+    }
+
+    /// Sets when the article was last changed.
+    pub fn modified_time(mut self, time: impl Into<AttrValue>) -> Self {
+        self.modified_time = Some(time.into());
+        self
+    }
+
+    /// Sets when the article is out of date.
+    pub fn expiration_time(mut self, time: impl Into<AttrValue>) -> Self {
+        self.expiration_time = Some(time.into());
+        self
+    }
+
+    /// Adds an author of the article.
+    pub fn author(mut self, author: impl Into<AttrValue>) -> Self {
+        self.authors.push(author.into());
+        self
+    }
+
+    /// Sets the section of the article.
+    pub fn section(mut self, section: impl Into<AttrValue>) -> Self {
+        self.section = Some(section.into());
+        self
+    }
+
+    /// Adds a tag to the article.
+    pub fn tag(mut self, tag: impl Into<AttrValue>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+}
+
+/// The `profile` properties of an Open Graph object.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Profile {
+    /// The first name of the person.
+    pub first_name: Option<AttrValue>,
+    /// The last name of the person.
+    pub last_name: Option<AttrValue>,
+    /// The username of the person.
+    pub username: Option<AttrValue>,
+    /// The gender of the person, `male` or `female`.
+    pub gender: Option<AttrValue>,
+}
+
+impl Profile {
+    /// Creates empty profile properties.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the first name of the person.
+    pub fn first_name(mut self, first_name: impl Into<AttrValue>) -> Self {
+        self.first_name = Some(first_name.into());
+        self
+    }
+
+    /// Sets the last name of the person.
+    pub fn last_name(mut self, last_name: impl Into<AttrValue>) -> Self {
+        self.last_name = Some(last_name.into());
+        self
+    }
+
+    /// Sets the username of the person.
+    pub fn username(mut self, username: impl Into<AttrValue>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    /// Sets the gender of the person.
+    pub fn gender(mut self, gender: impl Into<AttrValue>) -> Self {
+        self.gender = Some(gender.into());
+        self
+    }
+}
+
+/// The kind of a video object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VideoKind {
+    /// A movie.
+    Movie,
+    /// An episode of a TV show.
+    Episode,
+    /// A TV show.
+    TvShow,
+    /// Any other video.
+    #[default]
+    Other,
+}
+
+impl VideoKind {
+    /// Returns the value of the `og:type` property.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Movie => "video.movie",
+            Self::Episode => "video.episode",
+            Self::TvShow => "video.tv_show",
+            Self::Other => "video.other",
+        }
+    }
+}
+
+/// The `video` properties of an Open Graph object.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Video {
+    /// The kind of the video.
+    pub kind: VideoKind,
+    /// The actors of the video, as profile URLs.
+    pub actors: Vec<AttrValue>,
+    /// The directors of the video, as profile URLs.
+    pub directors: Vec<AttrValue>,
+    /// The length of the video, in seconds.
+    pub duration: Option<u32>,
+    /// When the video was released, as an ISO 8601 date time.
+    pub release_date: Option<AttrValue>,
+    /// The tags of the video.
+    pub tags: Vec<AttrValue>,
+}
+
+impl Video {
+    /// Creates empty `video.other` properties.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the kind of the video.
+    pub fn kind(mut self, kind: VideoKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Adds an actor of the video.
+    pub fn actor(mut self, actor: impl Into<AttrValue>) -> Self {
+        self.actors.push(actor.into());
+        self
+    }
+
+    /// Adds a director of the video.
+    pub fn director(mut self, director: impl Into<AttrValue>) -> Self {
+        self.directors.push(director.into());
+        self
+    }
+
+    /// Sets the length of the video, in seconds.
+    pub fn duration(mut self, duration: u32) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Sets when the video was released.
+    pub fn release_date(mut self, release_date: impl Into<AttrValue>) -> Self {
+        self.release_date = Some(release_date.into());
+        self
+    }
+
+    /// Adds a tag to the video.
+    pub fn tag(mut self, tag: impl Into<AttrValue>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+}
+
+/// The type of an Open Graph object, with its type-specific properties.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum OgType {
+    /// A website, the default type.
+    #[default]
+    Website,
+    /// An article, such as a blog post.
+    Article(Article),
+    /// The profile of a person.
+    Profile(Profile),
+    /// A video, such as a movie or an episode.
+    Video(Video),
+}
+
+/// An Open Graph object, rendered as `<meta property="og:...">` tags.
+///
+/// In debug builds, rendering it in the browser logs a warning when a required property, such as
+/// `og:image`, is missing.
+///
+/// # Example
+/// ```rust
+/// use next_rs::prelude::*;
+/// use next_rs::head::Head;
+/// use next_rs::open_graph::{Article, OgMedia, OpenGraph};
+///
+/// #[func]
+/// pub fn Post() -> Html {
+///     let open_graph = OpenGraph::article(Article::new().published_time("2024-01-20T08:00:00Z"))
+///         .title("Shipping Next RS")
+///         .url("https://acme.dev/blog/shipping")
+///         .image(OgMedia::new("https://acme.dev/og/shipping.png").size(1200, 630));
+///
+///     rsx! {
+///         <Head>{open_graph.to_html()}</Head>
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OpenGraph {
+    /// The type of the object, with its type-specific properties.
+    pub kind: OgType,
+    /// The title of the object.
+    pub title: Option<AttrValue>,
+    /// The description of the object.
+    pub description: Option<AttrValue>,
+    /// The canonical URL of the object.
+    pub url: Option<AttrValue>,
+    /// The name of the site the object belongs to.
+    pub site_name: Option<AttrValue>,
+    /// The locale of the object, such as `en_US`.
+    pub locale: Option<AttrValue>,
+    /// The other locales the object is available in.
+    pub alternate_locales: Vec<AttrValue>,
+    /// The images of the object, the first one being preferred.
+    pub images: Vec<OgMedia>,
+    /// The videos of the object.
+    pub videos: Vec<OgMedia>,
+}
+
+impl OpenGraph {
+    /// Creates an object of the given type, such as a video with
+    /// `OpenGraph::new(OgType::Video(video))`.
+    pub fn new(kind: OgType) -> Self {
+        Self {
+            kind,
+            ..Self::default()
+        }
+    }
+
+    /// Creates a `website` object.
+    pub fn website() -> Self {
+        Self::default()
+    }
+
+    /// Creates an `article` object.
+    pub fn article(article: Article) -> Self {
+        Self {
+            kind: OgType::Article(article),
+            ..Self::default()
+        }
+    }
+
+    /// Creates a `profile` object.
+    pub fn profile(profile: Profile) -> Self {
+        Self {
+            kind: OgType::Profile(profile),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the title of the object.
+    pub fn title(mut self, title: impl Into<AttrValue>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the description of the object.
+    pub fn description(mut self, description: impl Into<AttrValue>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the canonical URL of the object.
+    pub fn url(mut self, url: impl Into<AttrValue>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Sets the name of the site.
+    pub fn site_name(mut self, site_name: impl Into<AttrValue>) -> Self {
+        self.site_name = Some(site_name.into());
+        self
+    }
+
+    /// Sets the locale of the object.
+    pub fn locale(mut self, locale: impl Into<AttrValue>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Adds another locale the object is available in.
+    pub fn alternate_locale(mut self, locale: impl Into<AttrValue>) -> Self {
+        self.alternate_locales.push(locale.into());
+        self
+    }
+
+    /// Adds an image to the object.
+    pub fn image(mut self, image: OgMedia) -> Self {
+        self.images.push(image);
+        self
+    }
+
+    /// Adds a video to the object.
+    pub fn video(mut self, video: OgMedia) -> Self {
+        self.videos.push(video);
+        self
+    }
+
+    /// Returns the Open Graph type, such as `article`.
+    pub fn og_type(&self) -> &'static str {
+        match &self.kind {
+            OgType::Website => "website",
+            OgType::Article(_) => "article",
+            OgType::Profile(_) => "profile",
+            OgType::Video(video) => video.kind.as_str(),
+        }
+    }
+
+    /// Returns the required properties missing from the object, such as `og:image`.
+    pub fn missing_fields(&self) -> Vec<&'static str> {
+        let mut missing = Vec::new();
+        if self.title.is_none() {
+            missing.push("og:title");
+        }
+        if self.url.is_none() {
+            missing.push("og:url");
+        }
+        if self.images.is_empty() {
+            missing.push("og:image");
+        }
+        missing
+    }
+
+    /// Renders the object as meta tags.
+    pub fn to_html(&self) -> Html {
+        // The console is only reachable in the browser, not while rendering on the server.
+        if cfg!(all(debug_assertions, target_arch = "wasm32")) {
+            let missing = self.missing_fields();
+            if !missing.is_empty() {
+                log(&format!("Open Graph is missing {}", missing.join(", ")).into());
+            }
+        }
+
+        let mut tags = vec![property("og:type", self.og_type())];
+        tags.extend(self.title.clone().map(|title| property("og:title", title)));
+        tags.extend(
+            self.description
+                .clone()
+                .map(|text| property("og:description", text)),
+        );
+        tags.extend(self.url.clone().map(|url| property("og:url", url)));
+        tags.extend(
+            self.site_name
+                .clone()
+                .map(|site| property("og:site_name", site)),
+        );
+        tags.extend(
+            self.locale
+                .clone()
+                .map(|locale| property("og:locale", locale)),
+        );
+        for locale in &self.alternate_locales {
+            tags.push(property("og:locale:alternate", locale.clone()));
+        }
+        for image in &self.images {
+            image.to_html("og:image", &mut tags);
+        }
+        for video in &self.videos {
+            video.to_html("og:video", &mut tags);
+        }
+
+        match &self.kind {
+            OgType::Website => {}
+            OgType::Article(article) => {
+                let times = [
+                    ("article:published_time", &article.published_time),
+                    ("article:modified_time", &article.modified_time),
+                    ("article:expiration_time", &article.expiration_time),
+                ];
+                for (name, time) in times {
+                    tags.extend(time.clone().map(|time| property(name, time)));
+                }
+                for author in &article.authors {
+                    tags.push(property("article:author", author.clone()));
+                }
+                tags.extend(
+                    article
+                        .section
+                        .clone()
+                        .map(|s| property("article:section", s)),
+                );
+                for tag in &article.tags {
+                    tags.push(property("article:tag", tag.clone()));
+                }
+            }
+            OgType::Profile(profile) => {
+                let fields = [
+                    ("profile:first_name", &profile.first_name),
+                    ("profile:last_name", &profile.last_name),
+                    ("profile:username", &profile.username),
+                    ("profile:gender", &profile.gender),
+                ];
+                for (name, value) in fields {
+                    tags.extend(value.clone().map(|value| property(name, value)));
+                }
+            }
+            OgType::Video(video) => {
+                for actor in &video.actors {
+                    tags.push(property("video:actor", actor.clone()));
+                }
+                for director in &video.directors {
+                    tags.push(property("video:director", director.clone()));
+                }
+                tags.extend(
+                    video
+                        .duration
+                        .map(|d| property("video:duration", d.to_string())),
+                );
+                tags.extend(
+                    video
+                        .release_date
+                        .clone()
+                        .map(|d| property("video:release_date", d)),
+                );
+                for tag in &video.tags {
+                    tags.push(property("video:tag", tag.clone()));
+                }
+            }
+        }
+
+        tags.into_iter().collect()
+    }
+}
+
+/// The kind of a Twitter card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TwitterCardKind {
+    /// A title, description and thumbnail.
+    #[default]
+    Summary,
+    /// A summary with a large, prominent image.
+    SummaryLargeImage,
+    /// A card linking to a mobile app.
+    App,
+    /// A card playing a video or audio inline.
+    Player,
+}
+
+impl TwitterCardKind {
+    /// Returns the value of the `twitter:card` meta tag.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Summary => "summary",
+            Self::SummaryLargeImage => "summary_large_image",
+            Self::App => "app",
+            Self::Player => "player",
+        }
+    }
+}
+
+/// A Twitter card, rendered as `<meta name="twitter:...">` tags.
+///
+/// Fields left empty fall back to the matching Open Graph properties on Twitter's side. In debug
+/// builds, rendering it logs a warning when a field required by its kind is missing.
+///
+/// # Example
+/// ```
+/// use next_rs::open_graph::{TwitterCard, TwitterCardKind};
+///
+/// let card = TwitterCard::new(TwitterCardKind::SummaryLargeImage)
+///     .site("@acme")
+///     .image("https://acme.dev/og.png")
+///     .image_alt("The Acme logo");
+///
+/// assert!(card.missing_fields().is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TwitterCard {
+    /// The kind of the card.
+    pub card: TwitterCardKind,
+    /// The `@username` of the site.
+    pub site: Option<AttrValue>,
+    /// The `@username` of the content creator.
+    pub creator: Option<AttrValue>,
+    /// The title of the content.
+    pub title: Option<AttrValue>,
+    /// The description of the content.
+    pub description: Option<AttrValue>,
+    /// The URL of the image.
+    pub image: Option<AttrValue>,
+    /// A description of the image, for people who can't see it.
+    pub image_alt: Option<AttrValue>,
+    /// The HTTPS URL of the player iframe, for `player` cards.
+    pub player: Option<AttrValue>,
+    /// The width and height of the player, in pixels.
+    pub player_size: Option<(u32, u32)>,
+}
+
+impl TwitterCard {
+    /// Creates a card of the given kind.
+    pub fn new(card: TwitterCardKind) -> Self {
+        Self {
+            card,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the `@username` of the site.
+    pub fn site(mut self, site: impl Into<AttrValue>) -> Self {
+        self.site = Some(site.into());
+        self
+    }
+
+    /// Sets the `@username` of the content creator.
+    pub fn creator(mut self, creator: impl Into<AttrValue>) -> Self {
+        self.creator = Some(creator.into());
+        self
+    }
+
+    /// Sets the title of the content.
+    pub fn title(mut self, title: impl Into<AttrValue>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the description of the content.
+    pub fn description(mut self, description: impl Into<AttrValue>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the URL of the image.
+    pub fn image(mut self, image: impl Into<AttrValue>) -> Self {
+        self.image = Some(image.into());
+        self
+    }
+
+    /// Sets the description of the image.
+    pub fn image_alt(mut self, image_alt: impl Into<AttrValue>) -> Self {
+        self.image_alt = Some(image_alt.into());
+        self
+    }
+
+    /// Sets the URL and size of the player.
+    pub fn player(mut self, player: impl Into<AttrValue>, width: u32, height: u32) -> Self {
+        self.player = Some(player.into());
+        self.player_size = Some((width, height));
+        self
+    }
+
+    /// Returns the fields required by the kind of the card that are missing.
+    pub fn missing_fields(&self) -> Vec<&'static str> {
+        let mut missing = Vec::new();
+        match self.card {
+            TwitterCardKind::SummaryLargeImage if self.image.is_none() => {
+                missing.push("twitter:image")
+            }
+            TwitterCardKind::App if self.site.is_none() => missing.push("twitter:site"),
+            TwitterCardKind::Player => {
+                if self.player.is_none() {
+                    missing.push("twitter:player");
+                }
+                if self.image.is_none() {
+                    missing.push("twitter:image");
+                }
+            }
+            _ => {}
+        }
+        if self.image.is_some() && self.image_alt.is_none() {
+            missing.push("twitter:image:alt");
+        }
+        missing
+    }
+
+    /// Renders the card as meta tags.
+    pub fn to_html(&self) -> Html {
+        // The console is only reachable in the browser, not while rendering on the server.
+        if cfg!(all(debug_assertions, target_arch = "wasm32")) {
+            let missing = self.missing_fields();
+            if !missing.is_empty() {
+                log(&format!("Twitter card is missing {}", missing.join(", ")).into());
+            }
+        }
+
+        let mut tags = vec![name("twitter:card", self.card.as_str())];
+        let fields = [
+            ("twitter:site", &self.site),
+            ("twitter:creator", &self.creator),
+            ("twitter:title", &self.title),
+            ("twitter:description", &self.description),
+            ("twitter:image", &self.image),
+            ("twitter:image:alt", &self.image_alt),
+            ("twitter:player", &self.player),
+        ];
+        for (key, value) in fields {
+            tags.extend(value.clone().map(|value| name(key, value)));
+        }
+        if let Some((width, height)) = self.player_size {
+            tags.push(name("twitter:player:width", width.to_string()));
+            tags.push(name("twitter:player:height", height.to_string()));
+        }
+
+        tags.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::HeadEntry;
+
+    fn entries(html: Html) -> Vec<HeadEntry> {
+        match html {
+            Html::VList(list) => list.iter().filter_map(HeadEntry::from_html).collect(),
+            html => HeadEntry::from_html(&html).into_iter().collect(),
+        }
+    }
+
+    #[test]
+    fn incomplete_objects_render_natively() {
+        let open_graph = OpenGraph::website().title("Acme");
+        assert_eq!(open_graph.missing_fields(), ["og:url", "og:image"]);
+
+        let properties: Vec<String> = entries(open_graph.to_html())
+            .iter()
+            .filter_map(|entry| entry.attribute("property").map(str::to_string))
+            .collect();
+        assert_eq!(properties, ["og:type", "og:title"]);
+
+        let card = TwitterCard::new(TwitterCardKind::SummaryLargeImage);
+        assert!(!card.missing_fields().is_empty());
+        assert!(!entries(card.to_html()).is_empty());
+    }
+
+    /// Returns the `(property or name, content)` pairs of rendered tags, in order.
+    fn pairs(html: Html) -> Vec<(String, String)> {
+        entries(html)
+            .iter()
+            .map(|entry| {
+                let key = entry
+                    .attribute("property")
+                    .or_else(|| entry.attribute("name"))
+                    .unwrap_or_default();
+                let content = entry.attribute("content").unwrap_or_default();
+                (key.to_string(), content.to_string())
+            })
+            .collect()
+    }
+
+    fn expected(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(key, content)| (key.to_string(), content.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn article_properties_repeat_authors_and_tags() {
+        let open_graph = OpenGraph::article(
+            Article::new()
+                .published_time("2024-01-20T08:00:00Z")
+                .author("https://acme.dev/team/ada")
+                .author("https://acme.dev/team/linus")
+                .section("Engineering")
+                .tag("rust")
+                .tag("wasm"),
+        );
+
+        assert_eq!(
+            pairs(open_graph.to_html()),
+            expected(&[
+                ("og:type", "article"),
+                ("article:published_time", "2024-01-20T08:00:00Z"),
+                ("article:author", "https://acme.dev/team/ada"),
+                ("article:author", "https://acme.dev/team/linus"),
+                ("article:section", "Engineering"),
+                ("article:tag", "rust"),
+                ("article:tag", "wasm"),
+            ])
+        );
+    }
+
+    #[test]
+    fn profile_properties_are_rendered() {
+        let open_graph = OpenGraph::profile(
+            Profile::new()
+                .first_name("Ada")
+                .last_name("Lovelace")
+                .username("ada"),
+        );
+
+        assert_eq!(
+            pairs(open_graph.to_html()),
+            expected(&[
+                ("og:type", "profile"),
+                ("profile:first_name", "Ada"),
+                ("profile:last_name", "Lovelace"),
+                ("profile:username", "ada"),
+            ])
+        );
+    }
+
+    #[test]
+    fn every_image_and_video_keeps_its_structured_properties() {
+        let open_graph = OpenGraph::website()
+            .image(OgMedia::new("https://acme.dev/og.png").size(1200, 630))
+            .image(
+                OgMedia::new("https://acme.dev/square.png")
+                    .media_type("image/png")
+                    .alt("The Acme logo"),
+            )
+            .video(OgMedia::new("https://acme.dev/intro.mp4").size(1280, 720));
+
+        assert_eq!(
+            pairs(open_graph.to_html()),
+            expected(&[
+                ("og:type", "website"),
+                ("og:image", "https://acme.dev/og.png"),
+                ("og:image:width", "1200"),
+                ("og:image:height", "630"),
+                ("og:image", "https://acme.dev/square.png"),
+                ("og:image:type", "image/png"),
+                ("og:image:alt", "The Acme logo"),
+                ("og:video", "https://acme.dev/intro.mp4"),
+                ("og:video:width", "1280"),
+                ("og:video:height", "720"),
+            ])
+        );
+    }
+
+    #[test]
+    fn video_objects_are_typed_by_their_kind() {
+        assert_eq!(
+            OpenGraph::new(OgType::Video(Video::new())).og_type(),
+            "video.other"
+        );
+
+        let open_graph = OpenGraph::new(OgType::Video(
+            Video::new()
+                .kind(VideoKind::TvShow)
+                .director("https://acme.dev/team/ada")
+                .duration(3600),
+        ));
+        assert_eq!(
+            pairs(open_graph.to_html()),
+            expected(&[
+                ("og:type", "video.tv_show"),
+                ("video:director", "https://acme.dev/team/ada"),
+                ("video:duration", "3600"),
+            ])
+        );
+    }
+
+    #[test]
+    fn twitter_card_leaves_unset_fields_to_open_graph() {
+        let card = TwitterCard::new(TwitterCardKind::SummaryLargeImage)
+            .image("https://acme.dev/og.png")
+            .image_alt("The Acme logo");
+
+        assert!(card.missing_fields().is_empty());
+        assert_eq!(
+            pairs(card.to_html()),
+            expected(&[
+                ("twitter:card", "summary_large_image"),
+                ("twitter:image", "https://acme.dev/og.png"),
+                ("twitter:image:alt", "The Acme logo"),
+            ])
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use crate::head::Head;
+use crate::prelude::*;
+
+/// Renders a `<link rel href>` hint through the head manager.
+///
+/// Hints are deduplicated by `rel` and `href`, removed when the component rendering them
+/// unmounts, and rendered on the server like the other head elements.
+fn hint(rel: &'static str, href: &str, attributes: &[(&'static str, &str)]) -> Html {
+    if href.is_empty() {
+        return Html::default();
+    }
+    let attribute = |name: &str| {
+        attributes
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| AttrValue::from(value.to_string()))
+    };
+
+    rsx! {
+        <Head>
+            <link
+                rel={rel}
+                href={href.to_string()}
+                as={attribute("as")}
+                crossorigin={attribute("crossorigin")}
+            />
+        </Head>
+    }
+}
+
+/// Starts downloading a resource needed by the current page with a high priority.
+///
+/// Fonts are fetched in CORS mode, as browsers require.
+///
+/// # Arguments
+///
+/// * `href` - The URL of the resource.
+/// * `as_` - The kind of resource, such as `image`, `font`, `style`, `script` or `fetch`.
+///
+/// # Returns
+///
+/// (Html): The hint, to be rendered by the component needing the resource.
+///
+/// # Example
+/// ```rust
+/// use next_rs::prelude::*;
+/// use next_rs::resource_hints::preload;
+///
+/// #[func]
+/// pub fn Hero() -> Html {
+///     rsx! {
+///         <>
+///             {preload("/fonts/inter.woff2", "font")}
+///             <h1 class="font-inter">{"Next RS"}</h1>
+///         </>
+///     }
+/// }
+/// ```
+pub fn preload(href: &str, as_: &str) -> Html {
+    let crossorigin = match as_ {
+        "font" => Some(("crossorigin", "anonymous")),
+        _ => None,
+    };
+    let attributes: Vec<(&'static str, &str)> =
+        [("as", as_)].into_iter().chain(crossorigin).collect();
+    hint("preload", href, &attributes)
+}
+
+/// Opens a connection to an origin the page will fetch resources from.
+///
+/// # Arguments
+///
+/// * `origin` - The origin, such as `https://fonts.gstatic.com`.
+///
+/// # Returns
+///
+/// (Html): The hint, to be rendered by the component fetching from the origin.
+pub fn preconnect(origin: &str) -> Html {
+    hint("preconnect", origin, &[])
+}
+
+/// Resolves the DNS of an origin the page will fetch resources from, a cheaper
+/// [`preconnect`].
+///
+/// # Arguments
+///
+/// * `origin` - The origin, such as `https://cdn.example.com`.
+///
+/// # Returns
+///
+/// (Html): The hint, to be rendered by the component fetching from the origin.
+pub fn prefetch_dns(origin: &str) -> Html {
+    hint("dns-prefetch", origin, &[])
+}
+
+/// Downloads and parses a JavaScript module, such as the glue of a lazy route chunk, ahead of its
+/// import.
+///
+/// # Arguments
+///
+/// * `href` - The URL of the module.
+///
+/// # Returns
+///
+/// (Html): The hint, to be rendered by the component importing the module.
+pub fn modulepreload(href: &str) -> Html {
+    hint("modulepreload", href, &[])
+}
@@ -0,0 +1,382 @@
+use crate::head::Head;
+use crate::prelude::*;
+use std::fmt;
+
+/// The directives of a robots meta tag.
+///
+/// # Example
+/// ```
+/// use next_rs::robots::Robots;
+///
+/// let robots = Robots::new().noindex().max_image_preview("large");
+/// assert_eq!(robots.to_string(), "noindex, follow, max-image-preview:large");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Robots {
+    /// The crawler the directives apply to, such as `googlebot`, or `robots` for all of them.
+    pub bot: &'static str,
+    /// Whether the page may be indexed.
+    pub index: bool,
+    /// Whether the links of the page may be followed.
+    pub follow: bool,
+    /// Prevents showing a cached copy of the page.
+    pub noarchive: bool,
+    /// Prevents showing a snippet of the page.
+    pub nosnippet: bool,
+    /// Prevents indexing the images of the page.
+    pub noimageindex: bool,
+    /// Prevents offering a translation of the page.
+    pub notranslate: bool,
+    /// The maximum length of a text snippet, `-1` for no limit.
+    pub max_snippet: Option<i32>,
+    /// The maximum size of an image preview: `none`, `standard` or `large`.
+    pub max_image_preview: Option<&'static str>,
+    /// The maximum length of a video preview in seconds, `-1` for no limit.
+    pub max_video_preview: Option<i32>,
+    /// The date after which the page shouldn't be shown, such as `2025-12-31`.
+    pub unavailable_after: Option<AttrValue>,
+}
+
+impl Default for Robots {
+    fn default() -> Self {
+        Self {
+            bot: "robots",
+            index: true,
+            follow: true,
+            noarchive: false,
+            nosnippet: false,
+            noimageindex: false,
+            notranslate: false,
+            max_snippet: None,
+            max_image_preview: None,
+            max_video_preview: None,
+            unavailable_after: None,
+        }
+    }
+}
+
+impl Robots {
+    /// Creates directives allowing indexing and following links.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies the directives to a single crawler, such as `googlebot`.
+    pub fn bot(mut self, bot: &'static str) -> Self {
+        self.bot = bot;
+        self
+    }
+
+    /// Prevents indexing the page.
+    pub fn noindex(mut self) -> Self {
+        self.index = false;
+        self
+    }
+
+    /// Prevents following the links of the page.
+    pub fn nofollow(mut self) -> Self {
+        self.follow = false;
+        self
+    }
+
+    /// Prevents showing a cached copy of the page.
+    pub fn noarchive(mut self) -> Self {
+        self.noarchive = true;
+        self
+    }
+
+    /// Prevents showing a snippet of the page.
+    pub fn nosnippet(mut self) -> Self {
+        self.nosnippet = true;
+        self
+    }
+
+    /// Prevents indexing the images of the page.
+    pub fn noimageindex(mut self) -> Self {
+        self.noimageindex = true;
+        self
+    }
+
+    /// Prevents offering a translation of the page.
+    pub fn notranslate(mut self) -> Self {
+        self.notranslate = true;
+        self
+    }
+
+    /// Sets the maximum length of a text snippet.
+    pub fn max_snippet(mut self, length: i32) -> Self {
+        self.max_snippet = Some(length);
+        self
+    }
+
+    /// Sets the maximum size of an image preview: `none`, `standard` or `large`.
+    pub fn max_image_preview(mut self, size: &'static str) -> Self {
+        self.max_image_preview = Some(size);
+        self
+    }
+
+    /// Sets the maximum length of a video preview, in seconds.
+    pub fn max_video_preview(mut self, seconds: i32) -> Self {
+        self.max_video_preview = Some(seconds);
+        self
+    }
+
+    /// Sets the date after which the page shouldn't be shown.
+    pub fn unavailable_after(mut self, date: impl Into<AttrValue>) -> Self {
+        self.unavailable_after = Some(date.into());
+        self
+    }
+
+    /// Renders the directives as a `<meta name="robots">` tag, to be placed inside `Head`.
+    pub fn to_html(&self) -> Html {
+        rsx! { <meta name={self.bot} content={self.to_string()} /> }
+    }
+}
+
+impl fmt::Display for Robots {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut directives = vec![
+            if self.index { "index" } else { "noindex" }.to_string(),
+            if self.follow { "follow" } else { "nofollow" }.to_string(),
+        ];
+        let flags = [
+            (self.noarchive, "noarchive"),
+            (self.nosnippet, "nosnippet"),
+            (self.noimageindex, "noimageindex"),
+            (self.notranslate, "notranslate"),
+        ];
+        directives.extend(
+            flags
+                .into_iter()
+                .filter(|(set, _)| *set)
+                .map(|(_, flag)| flag.to_string()),
+        );
+        directives.extend(self.max_snippet.map(|n| format!("max-snippet:{}", n)));
+        directives.extend(
+            self.max_image_preview
+                .map(|size| format!("max-image-preview:{}", size)),
+        );
+        directives.extend(
+            self.max_video_preview
+                .map(|n| format!("max-video-preview:{}", n)),
+        );
+        directives.extend(
+            self.unavailable_after
+                .as_ref()
+                .map(|date| format!("unavailable_after:{}", date)),
+        );
+
+        write!(f, "{}", directives.join(", "))
+    }
+}
+
+impl From<Robots> for AttrValue {
+    fn from(robots: Robots) -> Self {
+        robots.to_string().into()
+    }
+}
+
+/// Renders the robots meta tag of the current route through `Head`.
+///
+/// Like other `Head` elements, the route mounted last wins, so a page can override the directives
+/// of its layout.
+///
+/// # Example
+/// ```rust
+/// use next_rs::prelude::*;
+/// use next_rs::robots::{robots, Robots};
+///
+/// #[func]
+/// pub fn Drafts() -> Html {
+///     rsx! {
+///         <>
+///             {robots(&Robots::new().noindex().nofollow())}
+///             <h1>{"Drafts"}</h1>
+///         </>
+///     }
+/// }
+/// ```
+pub fn robots(robots: &Robots) -> Html {
+    rsx! {
+        <Head>{robots.to_html()}</Head>
+    }
+}
+
+/// The rules of `robots.txt` for a group of crawlers.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RobotsRule {
+    /// The crawlers the rule applies to, such as `*` or `Googlebot`.
+    pub user_agents: Vec<String>,
+    /// The paths the crawlers may visit.
+    pub allow: Vec<String>,
+    /// The paths the crawlers may not visit.
+    pub disallow: Vec<String>,
+    /// The delay between requests, in seconds.
+    pub crawl_delay: Option<u32>,
+}
+
+impl RobotsRule {
+    /// Creates a rule for a crawler, such as `*` for all of them.
+    pub fn new(user_agent: impl Into<String>) -> Self {
+        Self {
+            user_agents: vec![user_agent.into()],
+            ..Self::default()
+        }
+    }
+
+    /// Applies the rule to another crawler.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agents.push(user_agent.into());
+        self
+    }
+
+    /// Allows a path.
+    pub fn allow(mut self, path: impl Into<String>) -> Self {
+        self.allow.push(path.into());
+        self
+    }
+
+    /// Disallows a path.
+    pub fn disallow(mut self, path: impl Into<String>) -> Self {
+        self.disallow.push(path.into());
+        self
+    }
+
+    /// Sets the delay between requests, in seconds.
+    pub fn crawl_delay(mut self, seconds: u32) -> Self {
+        self.crawl_delay = Some(seconds);
+        self
+    }
+}
+
+impl fmt::Display for RobotsRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for user_agent in &self.user_agents {
+            writeln!(f, "User-agent: {}", user_agent)?;
+        }
+        for path in &self.allow {
+            writeln!(f, "Allow: {}", path)?;
+        }
+        for path in &self.disallow {
+            writeln!(f, "Disallow: {}", path)?;
+        }
+        if let Some(delay) = self.crawl_delay {
+            writeln!(f, "Crawl-delay: {}", delay)?;
+        }
+        Ok(())
+    }
+}
+
+/// Produces the content of `robots.txt` from its rules and the URL of the sitemap.
+///
+/// Without any rule, every crawler is allowed everywhere.
+///
+/// # Example
+/// ```
+/// use next_rs::robots::{robots_txt, RobotsRule};
+///
+/// let rules = [RobotsRule::new("*").allow("/").disallow("/admin")];
+/// let content = robots_txt(&rules, Some("https://acme.dev/sitemap.xml"));
+///
+/// assert_eq!(
+///     content,
+///     "User-agent: *\nAllow: /\nDisallow: /admin\n\nSitemap: https://acme.dev/sitemap.xml\n"
+/// );
+/// ```
+pub fn robots_txt(rules: &[RobotsRule], sitemap: Option<&str>) -> String {
+    let mut groups: Vec<String> = rules.iter().map(ToString::to_string).collect();
+    if groups.is_empty() {
+        groups.push(RobotsRule::new("*").allow("/").to_string());
+    }
+
+    let mut content = groups.join("\n");
+    if let Some(sitemap) = sitemap {
+        content.push_str(&format!("\nSitemap: {}\n", sitemap));
+    }
+    content
+}
+
+/// Writes `robots.txt` into `dir`, such as the dist directory, from a build script or a native
+/// build step.
+///
+/// # Returns
+///
+/// A `Result` containing the path of the written file on success.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_robots_txt(
+    dir: impl AsRef<std::path::Path>,
+    rules: &[RobotsRule],
+    sitemap: Option<&str>,
+) -> std::io::Result<std::path::PathBuf> {
+    std::fs::create_dir_all(dir.as_ref())?;
+    let path = dir.as_ref().join("robots.txt");
+    std::fs::write(&path, robots_txt(rules, sitemap))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::HeadEntry;
+
+    #[test]
+    fn default_directives_allow_everything() {
+        assert_eq!(Robots::new().to_string(), "index, follow");
+    }
+
+    #[test]
+    fn directives_are_combined_in_order() {
+        let robots = Robots::new()
+            .nofollow()
+            .noarchive()
+            .notranslate()
+            .max_snippet(-1)
+            .max_image_preview("large")
+            .max_video_preview(30)
+            .unavailable_after("2025-12-31");
+
+        assert_eq!(
+            robots.to_string(),
+            "index, nofollow, noarchive, notranslate, max-snippet:-1, max-image-preview:large, \
+             max-video-preview:30, unavailable_after:2025-12-31"
+        );
+    }
+
+    #[test]
+    fn directives_target_their_bot() {
+        let robots = Robots::new().bot("googlebot").noindex().nosnippet();
+
+        assert_eq!(
+            HeadEntry::from_html(&robots.to_html()).map(|entry| entry.to_string()),
+            Some("<meta content=\"noindex, follow, nosnippet\" name=\"googlebot\">".to_string())
+        );
+        assert_eq!(
+            AttrValue::from(robots),
+            AttrValue::from("noindex, follow, nosnippet")
+        );
+    }
+
+    #[test]
+    fn robots_txt_lists_every_rule_then_the_sitemap() {
+        let rules = [
+            RobotsRule::new("Googlebot")
+                .user_agent("Bingbot")
+                .allow("/")
+                .disallow("/admin")
+                .disallow("/drafts"),
+            RobotsRule::new("*").disallow("/").crawl_delay(10),
+        ];
+
+        assert_eq!(
+            robots_txt(&rules, Some("https://acme.dev/sitemap.xml")),
+            "User-agent: Googlebot\nUser-agent: Bingbot\nAllow: /\nDisallow: /admin\n\
+             Disallow: /drafts\n\nUser-agent: *\nDisallow: /\nCrawl-delay: 10\n\n\
+             Sitemap: https://acme.dev/sitemap.xml\n"
+        );
+    }
+
+    #[test]
+    fn robots_txt_without_rules_allows_everything() {
+        assert_eq!(robots_txt(&[], None), "User-agent: *\nAllow: /\n");
+    }
+}
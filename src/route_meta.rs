@@ -0,0 +1,369 @@
+use crate::head::Head;
+use crate::prelude::*;
+use crate::router::{match_route, use_route, RouteParams};
+use crate::use_context;
+use std::rc::Rc;
+
+/// Metadata describing a route, such as its title and description.
+///
+/// It is rendered into the document head on navigation and can be read by any component through
+/// [`use_route_meta`], for instance to label navigation menus or report page views. Custom pairs
+/// are only meant for the app, so they are not rendered.
+///
+/// # Example
+/// ```
+/// use next_rs::route_meta::RouteMeta;
+///
+/// let meta = RouteMeta::new()
+///     .title("Blog")
+///     .description("Latest posts")
+///     .open_graph("image", "https://next-rs.dev/og/blog.png")
+///     .custom("nav_label", "Posts");
+///
+/// assert_eq!(meta.get("nav_label").map(|v| v.as_str()), Some("Posts"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RouteMeta {
+    /// The title of the page.
+    pub title: Option<AttrValue>,
+
+    /// The description of the page.
+    pub description: Option<AttrValue>,
+
+    /// Open Graph properties, such as `og:image`.
+    pub open_graph: Vec<(AttrValue, AttrValue)>,
+
+    /// The robots directives, such as `noindex, nofollow`.
+    pub robots: Option<AttrValue>,
+
+    /// Custom key/value pairs, read with [`RouteMeta::get`] and not rendered into the head.
+    pub custom: Vec<(AttrValue, AttrValue)>,
+}
+
+impl RouteMeta {
+    /// Creates empty route metadata.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the title of the page.
+    pub fn title(mut self, title: impl Into<AttrValue>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the description of the page.
+    pub fn description(mut self, description: impl Into<AttrValue>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Adds an Open Graph property. The `og:` prefix is added when missing.
+    pub fn open_graph(mut self, property: &str, content: impl Into<AttrValue>) -> Self {
+        let property = if property.starts_with("og:") {
+            property.to_string()
+        } else {
+            format!("og:{}", property)
+        };
+        self.open_graph.push((property.into(), content.into()));
+        self
+    }
+
+    /// Sets the robots directives of the page.
+    pub fn robots(mut self, robots: impl Into<AttrValue>) -> Self {
+        self.robots = Some(robots.into());
+        self
+    }
+
+    /// Adds a custom key/value pair.
+    pub fn custom(mut self, key: impl Into<AttrValue>, value: impl Into<AttrValue>) -> Self {
+        self.custom.push((key.into(), value.into()));
+        self
+    }
+
+    /// Returns the value of a custom key.
+    pub fn get(&self, key: &str) -> Option<&AttrValue> {
+        self.custom
+            .iter()
+            .find(|(name, _)| name.as_str() == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Renders the metadata as head tags, except for the title and the custom pairs.
+    pub fn to_html(&self) -> Html {
+        let description = self.description.as_ref().map(|description| {
+            rsx! { <meta name="description" content={description.clone()} /> }
+        });
+        let robots = self.robots.as_ref().map(|robots| {
+            rsx! { <meta name="robots" content={robots.clone()} /> }
+        });
+
+        rsx! {
+            <>
+                {for description}
+                {for self.open_graph.iter().map(|(property, content)| rsx! {
+                    <meta property={property.clone()} content={content.clone()} />
+                })}
+                {for robots}
+            </>
+        }
+    }
+}
+
+/// A route definition carrying the metadata of the pages it matches.
+///
+/// # Example
+/// ```rust,no_run
+/// use next_rs::prelude::*;
+/// use next_rs::router::*;
+/// use next_rs::route_meta::{Route, RouteMeta};
+///
+/// #[func]
+/// fn App() -> Html {
+///     let routes = vec![
+///         Route::new("/").meta(RouteMeta::new().title("Home")),
+///         Route::new("/posts/[id]").meta_with(|params| {
+///             RouteMeta::new().title(format!("Post {}", params["id"]))
+///         }),
+///     ];
+///
+///     rsx! {
+///         <NextRouter routes={routes}>
+///             <div />
+///         </NextRouter>
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Route {
+    /// The route pattern, using the syntax of [`match_route`].
+    pub path: &'static str,
+
+    /// The static metadata of the route.
+    pub meta: RouteMeta,
+
+    /// Builds the metadata from the params captured by the pattern, instead of `meta`.
+    pub meta_with: Option<Callback<RouteParams, RouteMeta>>,
+}
+
+/// Routes are compared by path, static metadata and identity of their `meta_with` callback, so a
+/// callback capturing data that changed invalidates the metadata of the router.
+impl PartialEq for Route {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.meta == other.meta && self.meta_with == other.meta_with
+    }
+}
+
+impl Route {
+    /// Creates a route definition without metadata.
+    pub fn new(path: &'static str) -> Self {
+        Self {
+            path,
+            meta: RouteMeta::default(),
+            meta_with: None,
+        }
+    }
+
+    /// Attaches static metadata to the route.
+    pub fn meta(mut self, meta: RouteMeta) -> Self {
+        self.meta = meta;
+        self
+    }
+
+    /// Attaches metadata derived from the route params.
+    pub fn meta_with<F>(mut self, meta: F) -> Self
+    where
+        F: Fn(RouteParams) -> RouteMeta + 'static,
+    {
+        self.meta_with = Some(Callback::from(meta));
+        self
+    }
+
+    /// Returns the metadata of the route for the captured params.
+    pub fn resolve_meta(&self, params: RouteParams) -> RouteMeta {
+        match &self.meta_with {
+            Some(meta_with) => meta_with.emit(params),
+            None => self.meta.clone(),
+        }
+    }
+}
+
+/// Resolves the metadata of the first route definition matching `path`.
+///
+/// # Arguments
+///
+/// * `routes` - The route definitions, in priority order.
+/// * `path` - The path to resolve, with the basename already stripped.
+///
+/// # Returns
+///
+/// (Option<RouteMeta>): The metadata of the matching route, if any.
+pub fn resolve_route_meta(routes: &[Route], path: &str) -> Option<RouteMeta> {
+    routes
+        .iter()
+        .find_map(|route| match_route(route.path, path).map(|params| route.resolve_meta(params)))
+}
+
+/// Represents the context of the route definitions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteMetaContext {
+    /// The route definitions of the router.
+    pub routes: Rc<Vec<Route>>,
+    /// The metadata of the current route.
+    pub current: Option<RouteMeta>,
+}
+
+/// Props for [`RouteMetaProvider`].
+#[derive(Properties, Clone, PartialEq)]
+pub struct RouteMetaProviderProps {
+    /// The route definitions, in priority order.
+    #[prop_or_default]
+    pub routes: Vec<Route>,
+    /// Children components to be rendered.
+    #[prop_or_default]
+    pub children: Html,
+}
+
+/// Provides the metadata of the current route and renders it into the document head.
+///
+/// It is rendered by the router with its `routes` prop, so it is rarely needed directly.
+#[func]
+pub fn RouteMetaProvider(props: &RouteMetaProviderProps) -> Html {
+    let route = use_route();
+    let routes = use_memo(props.routes.clone(), |routes| routes.clone());
+    let current = resolve_route_meta(&routes, &route);
+
+    let head = match &current {
+        Some(meta) => rsx! { <Head>{route_head(meta)}</Head> },
+        None => Html::default(),
+    };
+
+    let context = RouteMetaContext { routes, current };
+
+    rsx! {
+        <ContextProvider<RouteMetaContext> context={context}>
+            {head}
+            {props.children.clone()}
+        </ContextProvider<RouteMetaContext>>
+    }
+}
+
+/// Renders the metadata of a route as head elements, including its title.
+fn route_head(meta: &RouteMeta) -> Html {
+    let title = meta.title.as_ref().map(|title| {
+        rsx! { <title>{title.clone()}</title> }
+    });
+
+    rsx! {
+        <>
+            {for title}
+            {meta.to_html()}
+        </>
+    }
+}
+
+/// A hook to access the metadata of the current route.
+///
+/// It returns `None` outside of a router, or when no route definition matches the current route.
+#[hook]
+pub fn use_route_meta() -> Option<RouteMeta> {
+    use_context::<RouteMetaContext>()?.current
+}
+
+/// A hook to access every route definition of the router, for instance to build a menu.
+#[hook]
+pub fn use_routes() -> Rc<Vec<Route>> {
+    use_context::<RouteMetaContext>()
+        .map(|context| context.routes)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::head::map_components;
+    use crate::testing::{snapshot, HeadEntry};
+    use std::cell::Cell;
+
+    fn routes() -> Vec<Route> {
+        vec![
+            Route::new("/").meta(RouteMeta::new().title("Home")),
+            Route::new("/posts/new").meta(RouteMeta::new().title("New post")),
+            Route::new("/posts/[id]").meta_with(|params| {
+                RouteMeta::new()
+                    .title(format!("Post {}", params["id"]))
+                    .custom("id", params["id"].clone())
+            }),
+        ]
+    }
+
+    #[test]
+    fn the_first_matching_route_resolves_the_metadata() {
+        let routes = routes();
+        let title = |path: &str| resolve_route_meta(&routes, path).and_then(|meta| meta.title);
+
+        assert_eq!(title("/"), Some("Home".into()));
+        assert_eq!(title("/posts/new"), Some("New post".into()));
+        assert_eq!(title("/about"), None);
+        assert_eq!(resolve_route_meta(&[], "/"), None);
+    }
+
+    #[test]
+    fn meta_with_builds_the_metadata_from_the_params() {
+        let meta = resolve_route_meta(&routes(), "/posts/42").unwrap();
+        assert_eq!(meta.title, Some("Post 42".into()));
+        assert_eq!(meta.get("id").map(|id| id.as_str()), Some("42"));
+
+        let route = Route::new("/posts/[id]")
+            .meta(RouteMeta::new().title("Ignored"))
+            .meta_with(|_| RouteMeta::new().title("Dynamic"));
+        assert_eq!(
+            route.resolve_meta(RouteParams::new()).title,
+            Some("Dynamic".into())
+        );
+    }
+
+    #[test]
+    fn routes_with_another_meta_with_callback_are_not_equal() {
+        let calls = Rc::new(Cell::new(0));
+        let route = {
+            let calls = calls.clone();
+            Route::new("/posts/[id]").meta_with(move |_| {
+                calls.set(calls.get() + 1);
+                RouteMeta::new()
+            })
+        };
+
+        assert_eq!(route, route.clone());
+        assert_ne!(route, Route::new("/posts/[id]"));
+        assert_ne!(
+            route,
+            Route::new("/posts/[id]").meta_with(|_| RouteMeta::new())
+        );
+        assert_ne!(
+            Route::new("/").meta(RouteMeta::new().title("Home")),
+            Route::new("/").meta(RouteMeta::new().title("Start"))
+        );
+        assert_eq!(calls.get(), 0);
+    }
+
+    #[test]
+    fn route_head_renders_everything_but_custom_pairs() {
+        let meta = RouteMeta::new()
+            .title("Blog")
+            .description("Latest posts")
+            .open_graph("image", "https://next-rs.dev/og/blog.png")
+            .robots("noindex")
+            .custom("nav_label", "Posts");
+        let head = map_components(vec![route_head(&meta)]);
+
+        assert_eq!(
+            snapshot(head.iter().filter_map(HeadEntry::from_html)),
+            "<meta charset=\"utf-8\">\n\
+             <meta content=\"Latest posts\" name=\"description\">\n\
+             <meta content=\"https://next-rs.dev/og/blog.png\" property=\"og:image\">\n\
+             <meta content=\"noindex\" name=\"robots\">\n\
+             <title>Blog</title>"
+        );
+    }
+}
@@ -1,19 +1,45 @@
 use crate::log;
 use std::borrow::Cow;
 
+use crate::dynamic::{prefetch_chunk, register_lazy_routes, LazyFallback, LazyRoute};
+use crate::head::HeadProvider;
 use crate::history::{AnyHistory, BrowserHistory, History, HistoryError, HistoryResult};
 use crate::prelude::*;
+use crate::route_meta::{Route, RouteMetaProvider};
 use crate::use_context;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Deref;
 use std::rc::Rc;
+use yew::html::IntoPropValue;
 use yew_router::prelude::Location;
 
 use gloo_net::http::Request;
-use web_sys::{EventListener, RequestCache};
+use web_sys::js_sys::{Object, Reflect};
+use web_sys::wasm_bindgen::JsCast;
+use web_sys::{window, CustomEvent, CustomEventInit, EventListener, RequestCache};
 
 use wasm_bindgen_futures::spawn_local;
-use web_sys::js_sys::Function;
+
+/// Dispatches a router event, such as `route_change_complete`, on the window, with the path as
+/// its detail, so listeners run without evaluating code under a Content Security Policy.
+fn emit_route_event(name: &str, as_path: &str) {
+    let init = CustomEventInit::new();
+    init.set_detail(&as_path.into());
+    let event = match CustomEvent::new_with_event_init_dict(name, &init) {
+        Ok(event) => event,
+        Err(err) => {
+            log(&format!("Error creating router event {}: {:?}", name, err).into());
+            return;
+        }
+    };
+    if let Some(win) = window() {
+        let _ = win.dispatch_event(&event);
+    }
+}
 
 /// Represents errors related to navigation.
 pub type NavigationError = HistoryError;
@@ -21,6 +47,151 @@ pub type NavigationError = HistoryError;
 /// Represents results of navigation operations.
 pub type NavigationResult<T> = HistoryResult<T>;
 
+/// The version of the [`HistoryState`] format, bumped whenever the stored layout changes.
+pub const HISTORY_STATE_VERSION: u32 = 1;
+
+// Property of `window.history.state` holding the JSON of the [`HistoryState`], next to the
+// `__history_id` of the entry.
+static HISTORY_STATE_KEY: &str = "__next_rs_state";
+
+/// The state stored in a history entry by the [`Router`].
+///
+/// States are serialized to JSON when pushed, so every navigation method stores them the same
+/// way regardless of their Rust type, and reading them back never depends on the exact type that
+/// was pushed. They are pushed through the history like any other state, so
+/// [`Location::state`] keeps working. With browser and hash histories, the JSON is also stored
+/// in `window.history.state`, next to the id of the entry, so it survives reloads and is restored
+/// on back and forward navigation, including by a newer build of the app, which ignores states
+/// stored with another [`HISTORY_STATE_VERSION`].
+///
+/// # Example
+/// ```
+/// use next_rs::router::HistoryState;
+///
+/// let json = HistoryState::new(&vec!["a", "b"]).to_json();
+/// let state = HistoryState::from_json(&json).unwrap();
+/// assert_eq!(state.value::<Vec<String>>(), Some(vec!["a".into(), "b".into()]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryState {
+    /// The version of the format the state was stored with.
+    pub version: u32,
+    /// The serialized state.
+    pub value: Value,
+}
+
+impl HistoryState {
+    /// Serializes `state` into a new history state.
+    ///
+    /// States that fail to serialize are logged and stored as `null`.
+    pub fn new<T: Serialize + ?Sized>(state: &T) -> Self {
+        let value = serde_json::to_value(state).unwrap_or_else(|err| {
+            if cfg!(target_arch = "wasm32") {
+                log(&format!("Failed to serialize history state: {}", err).into());
+            }
+            Value::Null
+        });
+
+        Self {
+            version: HISTORY_STATE_VERSION,
+            value,
+        }
+    }
+
+    /// Deserializes the stored state.
+    ///
+    /// # Returns
+    ///
+    /// (Option<T>): The state, or `None` if it was stored with another format version or no
+    /// longer matches the shape of `T`.
+    pub fn value<T: DeserializeOwned>(&self) -> Option<T> {
+        if self.version != HISTORY_STATE_VERSION {
+            if cfg!(target_arch = "wasm32") {
+                log(&format!(
+                    "Ignoring history state stored with version {}, expected {}",
+                    self.version, HISTORY_STATE_VERSION
+                )
+                .into());
+            }
+            return None;
+        }
+
+        match serde_json::from_value(self.value.clone()) {
+            Ok(state) => Some(state),
+            Err(err) => {
+                if cfg!(target_arch = "wasm32") {
+                    log(&format!(
+                        "Ignoring history state that no longer deserializes: {}",
+                        err
+                    )
+                    .into());
+                }
+                None
+            }
+        }
+    }
+
+    /// Serializes the state to the JSON stored in `window.history.state`.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Parses a state serialized with [`HistoryState::to_json`].
+    ///
+    /// # Returns
+    ///
+    /// (Option<HistoryState>): The state, or `None` if `json` wasn't stored by the router.
+    pub fn from_json(json: &str) -> Option<Self> {
+        serde_json::from_str(json).ok()
+    }
+
+    /// Returns the state stored in the current entry of the browser history, if any.
+    pub fn current() -> Option<Self> {
+        if !cfg!(target_arch = "wasm32") {
+            return None;
+        }
+        let state = window()?.history().ok()?.state().ok()?;
+        let json = Reflect::get(&state, &HISTORY_STATE_KEY.into()).ok()?;
+        Self::from_json(&json.as_string()?)
+    }
+
+    /// Stores the state in the current entry of the browser history, next to the properties
+    /// already set by the history, such as the id of the entry, and keeping its URL.
+    fn store(&self) {
+        if !cfg!(target_arch = "wasm32") {
+            return;
+        }
+        let history = match window().and_then(|win| win.history().ok()) {
+            Some(history) => history,
+            None => return,
+        };
+        let state = match history.state() {
+            Ok(state) if state.is_object() => state.unchecked_into::<Object>(),
+            _ => Object::new(),
+        };
+        let stored = Reflect::set(&state, &HISTORY_STATE_KEY.into(), &self.to_json().into())
+            .and_then(|_| history.replace_state(&state, ""));
+        if let Err(err) = stored {
+            log(&format!("Failed to store history state: {:?}", err).into());
+        }
+    }
+}
+
+/// Pushes or replaces a history entry with `state`.
+///
+/// The state goes through the history, so [`Location::state`] returns it. Browser and hash
+/// histories only keep it in memory, so it is also stored in `window.history.state` to survive
+/// reloads.
+fn navigate_history(history: &AnyHistory, route: String, replace: bool, state: HistoryState) {
+    match replace {
+        true => history.replace_with_state(route, state.clone()),
+        false => history.push_with_state(route, state.clone()),
+    }
+    if !matches!(history, AnyHistory::Memory(_)) {
+        state.store();
+    }
+}
+
 /// Represents the context of the current location.
 #[derive(Clone)]
 pub struct LocationContext {
@@ -81,6 +252,15 @@ pub struct RouterProps {
     /// The base URL for the router.
     #[prop_or_default]
     pub basename: &'static str,
+    /// Routes whose components are loaded from separate wasm chunks.
+    #[prop_or_default]
+    pub lazy_routes: Vec<LazyRoute>,
+    /// The content displayed while the chunk of a lazy route is being fetched.
+    #[prop_or_default]
+    pub lazy_fallback: Html,
+    /// Route definitions carrying the metadata of the pages they match.
+    #[prop_or_default]
+    pub routes: Vec<Route>,
 }
 
 /// The kind of Router Provider.
@@ -116,7 +296,7 @@ pub struct Router {
     basename: &'static str,
 
     /// The current route of the router.
-    route: &'static str,
+    route: Href,
 
     /// A mapping of route names to corresponding component information.
     components: HashMap<&'static str, ComponentInfo>,
@@ -202,7 +382,7 @@ impl Router {
         Self {
             history,
             basename,
-            route,
+            route: route.into(),
             components,
             fetching_routes,
             events,
@@ -246,9 +426,9 @@ impl Router {
     /// # Arguments
     ///
     /// * `route` - The route to be pushed.
-    pub fn push(&mut self, route: &'static str) {
-        self.route = route;
-        self.history.push(self.prefix_basename(route));
+    pub fn push(&mut self, route: impl Into<Href>) {
+        self.route = route.into();
+        self.history.push(self.prefix_basename(&self.route));
     }
 
     /// Replaces the current history entry with the provided route.
@@ -256,21 +436,22 @@ impl Router {
     /// # Arguments
     ///
     /// * `route` - The route to replace the current history entry.
-    pub fn replace(&mut self, route: &'static str) {
-        self.route = route;
-        self.history.replace(self.prefix_basename(route));
+    pub fn replace(&mut self, route: impl Into<Href>) {
+        self.route = route.into();
+        self.history.replace(self.prefix_basename(&self.route));
     }
 
     /// Pushes a route onto the history stack with state.
     ///
+    /// The state is stored as a [`HistoryState`] and can be read back with [`use_history_state`].
+    ///
     /// # Arguments
     ///
     /// * `route` - The route to be pushed.
     /// * `state` - The state to be associated with the route.
-    pub fn push_with_state(&mut self, route: &'static str, state: &'static str) {
-        self.route = route;
-        self.history
-            .push_with_state(self.prefix_basename(route), state);
+    pub fn push_with_state<T: Serialize + ?Sized>(&mut self, route: impl Into<Href>, state: &T) {
+        self.route = route.into();
+        self.navigate_with_state(false, HistoryState::new(state));
     }
 
     /// Replaces the current history entry with the provided route and state.
@@ -279,10 +460,9 @@ impl Router {
     ///
     /// * `route` - The route to replace the current history entry.
     /// * `state` - The state to be associated with the route.
-    pub fn replace_with_state(&mut self, route: &'static str, state: &'static str) {
-        self.route = route;
-        self.history
-            .replace_with_state(self.prefix_basename(route), state);
+    pub fn replace_with_state<T: Serialize + ?Sized>(&mut self, route: impl Into<Href>, state: &T) {
+        self.route = route.into();
+        self.navigate_with_state(true, HistoryState::new(state));
     }
 
     /// Pushes a route onto the history stack with query parameters.
@@ -294,12 +474,11 @@ impl Router {
     ///
     /// # Returns
     ///
-    /// A `NavigationResult` indicating the success of the operation.
+    /// A `NavigationResult` indicating the success of the operation, with the [`UrlError`]
+    /// raised while building the href otherwise.
+    #[deprecated(note = "use `Router::try_push_with_query`, which accepts any serializable query")]
     pub fn push_with_query(&mut self, route: &'static str, query: &Value) -> NavigationResult<()> {
-        self.route = route;
-        self.query = query.clone();
-        self.history
-            .push_with_query(self.prefix_basename(route), query)
+        Ok(self.try_push_with_query(route, query)?)
     }
 
     /// Pushes a route onto the history stack with query parameters and state.
@@ -312,17 +491,18 @@ impl Router {
     ///
     /// # Returns
     ///
-    /// A `NavigationResult` indicating the success of the operation.
+    /// A `NavigationResult` indicating the success of the operation, with the [`UrlError`]
+    /// raised while building the href otherwise.
+    #[deprecated(
+        note = "use `Router::try_push_with_query_and_state`, which accepts any serializable query and state"
+    )]
     pub fn push_with_query_and_state(
         &mut self,
         route: &'static str,
         query: &Value,
         state: &'static str,
     ) -> NavigationResult<()> {
-        self.route = route;
-        self.query = query.clone();
-        self.history
-            .push_with_query_and_state(self.prefix_basename(route), query, state)
+        Ok(self.try_push_with_query_and_state(route, query, state)?)
     }
 
     /// Replaces the current history entry with the provided route, query parameters, and state.
@@ -335,17 +515,102 @@ impl Router {
     ///
     /// # Returns
     ///
-    /// A `NavigationResult` indicating the success of the operation.
+    /// A `NavigationResult` indicating the success of the operation, with the [`UrlError`]
+    /// raised while building the href otherwise.
+    #[deprecated(
+        note = "use `Router::try_replace_with_query_and_state`, which accepts any serializable query and state"
+    )]
     pub fn replace_with_query_and_state(
         &mut self,
         route: &'static str,
         query: &Value,
         state: Value,
     ) -> NavigationResult<()> {
-        self.route = route;
-        self.query = query.clone();
-        self.history
-            .replace_with_query_and_state(self.prefix_basename(route), query, state)
+        Ok(self.try_replace_with_query_and_state(route, query, &state)?)
+    }
+
+    /// Pushes a route onto the history stack with query parameters.
+    ///
+    /// The query is encoded like [`Url::query`], so nested objects are supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `route` - The route to be pushed.
+    /// * `query` - The query parameters to be associated with the route.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating the success of the operation.
+    pub fn try_push_with_query<Q: Serialize + ?Sized>(
+        &mut self,
+        route: impl Into<Href>,
+        query: &Q,
+    ) -> Result<(), UrlError> {
+        self.set_route_with_query(route.into(), query)?;
+        self.history.push(self.prefix_basename(&self.route));
+        Ok(())
+    }
+
+    /// Pushes a route onto the history stack with query parameters and state.
+    ///
+    /// # Arguments
+    ///
+    /// * `route` - The route to be pushed.
+    /// * `query` - The query parameters to be associated with the route.
+    /// * `state` - The state to be associated with the route.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating the success of the operation.
+    pub fn try_push_with_query_and_state<Q: Serialize + ?Sized, T: Serialize + ?Sized>(
+        &mut self,
+        route: impl Into<Href>,
+        query: &Q,
+        state: &T,
+    ) -> Result<(), UrlError> {
+        self.set_route_with_query(route.into(), query)?;
+        self.navigate_with_state(false, HistoryState::new(state));
+        Ok(())
+    }
+
+    /// Replaces the current history entry with the provided route, query parameters, and state.
+    ///
+    /// # Arguments
+    ///
+    /// * `route` - The route to replace the current history entry.
+    /// * `query` - The query parameters to be associated with the route.
+    /// * `state` - The state to be associated with the route.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating the success of the operation.
+    pub fn try_replace_with_query_and_state<Q: Serialize + ?Sized, T: Serialize + ?Sized>(
+        &mut self,
+        route: impl Into<Href>,
+        query: &Q,
+        state: &T,
+    ) -> Result<(), UrlError> {
+        self.set_route_with_query(route.into(), query)?;
+        self.navigate_with_state(true, HistoryState::new(state));
+        Ok(())
+    }
+
+    /// Pushes or replaces a history entry for the current route, storing `state` in it.
+    fn navigate_with_state(&self, replace: bool, state: HistoryState) {
+        let route = self.prefix_basename(&self.route).into_owned();
+        navigate_history(&self.history, route, replace, state);
+    }
+
+    /// Stores the route with the query appended, along with the query itself.
+    fn set_route_with_query<Q: Serialize + ?Sized>(
+        &mut self,
+        route: Href,
+        query: &Q,
+    ) -> Result<(), UrlError> {
+        self.query =
+            serde_json::to_value(query).map_err(|err| UrlError::InvalidQuery(err.to_string()))?;
+        self.route = Url::new(route.as_str()).query(&self.query).build()?;
+        Ok(())
     }
 
     /// Returns the kind of the router.
@@ -412,10 +677,16 @@ impl Router {
 
     /// Prefetches the specified URL by fetching its route information.
     ///
+    /// Routes registered as lazy routes warm their wasm chunk instead.
+    ///
     /// # Arguments
     ///
     /// * `url` - The URL to prefetch.
-    pub fn prefetch(&mut self, url: &'static str) {
+    pub fn prefetch(&mut self, url: impl Into<Href>) {
+        let url = url.into();
+        if prefetch_chunk(&url) {
+            return;
+        }
         self.fetch_route(url.to_string());
     }
 
@@ -455,51 +726,40 @@ impl Router {
     /// * `route` - The route to fetch.
     fn fetch_route(&mut self, route: String) {
         let url = format!("/{}/index.json", route);
-        let events = EventListener::new();
         let subscriptions = self.subscriptions.clone();
         let as_path = self.as_path;
         let route = route.clone();
-        let self_route = self.route;
+        let self_route = self.route.clone();
         let fetching_routes = Callback::from(move |_: String| {
             let url = url.clone();
             let mut fetching_routes = HashSet::new();
-            let mut events = events.clone();
             let subscriptions = subscriptions.clone();
             let as_path = as_path;
             let route = route.clone();
-            let self_route = self_route;
+            let self_route = self_route.clone();
             spawn_local(async move {
                 let result = match Self::fetch_gloo_net(&url).await {
                     Ok(component_info) => {
                         fetching_routes.insert(route.clone());
-                        if self_route == route {
+                        if *self_route == *route {
                             if !component_info.err.is_empty() {
-                                events.handle_event(&Function::new_with_args(
-                                    "route_change_error",
-                                    as_path,
-                                ));
+                                emit_route_event("route_change_error", as_path);
                             }
                             Self::notify(subscriptions, component_info);
-                            events.handle_event(&Function::new_with_args(
-                                "route_change_complete",
-                                as_path,
-                            ));
+                            emit_route_event("route_change_complete", as_path);
                         }
                         Ok(())
                     }
                     Err(fetch_error) => {
                         fetching_routes.insert(route.clone());
                         log(&format!("Error fetching route: {:?}", fetch_error).into());
-                        if self_route == route {
+                        if *self_route == *route {
                             let component_info = ComponentInfo {
                                 component: rsx! {},
                                 err: "Error fetching route",
                             };
                             Self::notify(subscriptions, component_info);
-                            events.handle_event(&Function::new_with_args(
-                                "route_change_complete",
-                                as_path,
-                            ));
+                            emit_route_event("route_change_complete", as_path);
                         }
                         Err(fetch_error)
                     }
@@ -580,8 +840,13 @@ pub fn BaseRouter(props: &RouterProps) -> Html {
         history,
         children,
         basename,
+        lazy_routes,
+        lazy_fallback,
+        routes,
     } = props.clone();
 
+    use_effect_with(lazy_routes, |lazy_routes| register_lazy_routes(lazy_routes));
+
     let loc_ctx = use_reducer(|| LocationContext {
         location: history.location(),
         ctr: 0,
@@ -650,12 +915,18 @@ pub fn BaseRouter(props: &RouterProps) -> Html {
     }
 
     rsx! {
-        <ContextProvider<RouterContext> context={navi_ctx}>
-            <ContextProvider<LocationContext> context={(*loc_ctx).clone()}>
-                {children}
-                {component_value}
-            </ContextProvider<LocationContext>>
-        </ContextProvider<RouterContext>>
+        <HeadProvider>
+            <ContextProvider<RouterContext> context={navi_ctx}>
+                <ContextProvider<LocationContext> context={(*loc_ctx).clone()}>
+                    <ContextProvider<LazyFallback> context={LazyFallback(lazy_fallback)}>
+                        <RouteMetaProvider routes={routes}>
+                            {children}
+                            {component_value}
+                        </RouteMetaProvider>
+                    </ContextProvider<LazyFallback>>
+                </ContextProvider<LocationContext>>
+            </ContextProvider<RouterContext>>
+        </HeadProvider>
     }
 }
 
@@ -794,3 +1065,701 @@ pub fn use_route() -> Cow<'static, str> {
 
     stripped_path
 }
+
+/// A hook to read the state of the current history entry.
+///
+/// The state is whatever was pushed with [`Router::push_with_state`] or the other state-aware
+/// navigation methods, read back from `window.history.state` after reloads and back or forward
+/// navigation. It returns `None` when the entry has no state, when it was stored with another
+/// [`HISTORY_STATE_VERSION`], or when it can no longer be deserialized into `T`, for instance
+/// after its shape changed.
+///
+/// # Example
+/// ```rust,no_run
+/// use next_rs::prelude::*;
+/// use next_rs::router::use_history_state;
+///
+/// #[func]
+/// pub fn MyComponent() -> Html {
+///     let selected = use_history_state::<String>().unwrap_or_default();
+///
+///     rsx! {
+///         <p>{ selected }</p>
+///     }
+/// }
+/// ```
+#[hook]
+pub fn use_history_state<T>() -> Option<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    // Re-read on every location change, including `popstate`.
+    let location = use_location()?;
+
+    match HistoryState::current() {
+        Some(state) => state.value(),
+        None => location.state::<HistoryState>()?.value(),
+    }
+}
+
+/// Checks whether `path` points at the `current` route.
+///
+/// Query strings and hash fragments of `path` are ignored, as are trailing slashes. Anchors,
+/// external URLs and relative paths are never considered active.
+///
+/// # Arguments
+///
+/// * `current` - The current route, with the basename already stripped.
+/// * `path` - The path to compare against the current route.
+/// * `exact` - Whether the whole route must match, instead of a segment-wise prefix.
+///
+/// # Returns
+///
+/// (bool): `true` if `path` is the current route or, unless `exact` is set, one of its parents.
+///
+/// # Example
+/// ```
+/// use next_rs::router::is_active_path;
+///
+/// assert!(is_active_path("/blog/post-1", "/blog", false));
+/// assert!(!is_active_path("/blog/post-1", "/blog", true));
+/// assert!(!is_active_path("/blogging", "/blog", false));
+/// ```
+pub fn is_active_path(current: &str, path: &str, exact: bool) -> bool {
+    if path.starts_with('#') || path.starts_with("/#") {
+        return false;
+    }
+
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    if !path.starts_with('/') || path.starts_with("//") {
+        return false;
+    }
+
+    let path = path.trim_end_matches('/');
+    let current = current.trim_end_matches('/');
+
+    if exact || path.is_empty() {
+        // The root route only matches itself, otherwise it would be active everywhere.
+        current == path
+    } else {
+        current == path
+            || current
+                .strip_prefix(path)
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+}
+
+/// A hook to check whether a path matches the current route.
+///
+/// This hook is meant for menus that render their own anchors, such as the `Navbar` and `Sidebar`
+/// components. It compares `path` against the route returned by [`use_route`] using prefix
+/// matching, so `/blog` stays active while browsing `/blog/post-1`.
+#[hook]
+pub fn use_is_active(path: &'static str) -> bool {
+    let route = use_route();
+
+    is_active_path(&route, path, false)
+}
+
+// Characters left as-is when percent-encoding; everything else is escaped.
+const UNRESERVED: &str = "-._~";
+const FRAGMENT_SAFE: &str = "-._~/?:@!$'()*,;=";
+
+/// Percent-encodes `input`, keeping ASCII alphanumerics and the characters in `keep`.
+fn percent_encode(input: &str, keep: &str) -> String {
+    let mut encoded = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        if byte.is_ascii_alphanumeric() || keep.as_bytes().contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Decodes percent-encoded sequences of `input`, leaving invalid ones untouched.
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = bytes
+            .get(index + 1..index + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// The params captured from the dynamic segments of a route pattern.
+pub type RouteParams = HashMap<String, String>;
+
+/// Matches `path` against a route pattern and captures its params.
+///
+/// Patterns use the same syntax as [`Url`]: `[name]` or `:name` for dynamic segments, `[...name]`
+/// for catch-all segments and `[[...name]]` for optional catch-all segments. Captured values are
+/// percent-decoded, and catch-all values keep their inner slashes.
+///
+/// # Arguments
+///
+/// * `pattern` - The route pattern.
+/// * `path` - The path to match, with the basename already stripped.
+///
+/// # Returns
+///
+/// (Option<RouteParams>): The captured params, or `None` if the path does not match.
+///
+/// # Example
+/// ```
+/// use next_rs::router::match_route;
+///
+/// let params = match_route("/posts/[id]/[...rest]", "/posts/42/a/b%20c").unwrap();
+/// assert_eq!(params["id"], "42");
+/// assert_eq!(params["rest"], "a/b c");
+/// assert!(match_route("/posts/[id]", "/users/42").is_none());
+/// ```
+pub fn match_route(pattern: &str, path: &str) -> Option<RouteParams> {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let patterns: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let mut params = RouteParams::new();
+
+    for (index, pattern) in patterns.iter().enumerate() {
+        let optional = pattern
+            .strip_prefix("[[...")
+            .and_then(|s| s.strip_suffix("]]"));
+        let required = pattern
+            .strip_prefix("[...")
+            .and_then(|s| s.strip_suffix(']'));
+        if let Some(name) = optional.or(required) {
+            let rest = segments.get(index..).unwrap_or_default();
+            if rest.is_empty() && required.is_some() {
+                return None;
+            }
+            let value: Vec<String> = rest.iter().map(|s| percent_decode(s)).collect();
+            params.insert(name.to_string(), value.join("/"));
+            return Some(params);
+        }
+
+        let segment = segments.get(index)?;
+        if let Some(name) = pattern
+            .strip_prefix('[')
+            .and_then(|s| s.strip_suffix(']'))
+            .or_else(|| pattern.strip_prefix(':'))
+        {
+            params.insert(name.to_string(), percent_decode(segment));
+        } else if pattern != segment {
+            return None;
+        }
+    }
+
+    (patterns.len() == segments.len()).then_some(params)
+}
+
+/// Flattens a query value into key/value pairs, sorting object keys.
+///
+/// Nested objects use bracketed keys (`filter[tag]=rust`), scalar arrays repeat their key
+/// (`tag=a&tag=b`) and arrays of objects or arrays are indexed (`items[0][id]=1`).
+fn flatten_query(key: &str, value: &Value, pairs: &mut Vec<(String, String)>) {
+    match value {
+        Value::Null => {}
+        Value::Bool(value) => pairs.push((key.to_string(), value.to_string())),
+        Value::Number(value) => pairs.push((key.to_string(), value.to_string())),
+        Value::String(value) => pairs.push((key.to_string(), value.clone())),
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                if item.is_array() || item.is_object() {
+                    flatten_query(&format!("{key}[{index}]"), item, pairs);
+                } else {
+                    flatten_query(key, item, pairs);
+                }
+            }
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(name, _)| *name);
+            for (name, item) in entries {
+                if key.is_empty() {
+                    flatten_query(name, item, pairs);
+                } else {
+                    flatten_query(&format!("{key}[{name}]"), item, pairs);
+                }
+            }
+        }
+    }
+}
+
+/// Represents errors raised while building a [`Url`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlError {
+    /// A dynamic segment of the route pattern has no matching param.
+    MissingParam(String),
+    /// The query could not be serialized into key/value pairs.
+    InvalidQuery(String),
+}
+
+impl fmt::Display for UrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UrlError::MissingParam(name) => write!(f, "missing route param `{}`", name),
+            UrlError::InvalidQuery(err) => write!(f, "invalid query: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for UrlError {}
+
+impl From<UrlError> for NavigationError {
+    /// Wraps the error as a query serialization error, the only kind of [`NavigationError`],
+    /// keeping its message, for the navigation methods returning a `NavigationResult`.
+    fn from(err: UrlError) -> Self {
+        NavigationError::QuerySer(serde::ser::Error::custom(err))
+    }
+}
+
+/// A route href, as accepted by the [`Router`] methods and [`crate::LinkProps::to`].
+///
+/// It can be created from a string literal, an owned `String` or a [`Url`] builder.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Href(AttrValue);
+
+impl Href {
+    /// Returns the href as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for Href {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Display for Href {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&'static str> for Href {
+    fn from(href: &'static str) -> Self {
+        Self(AttrValue::from(href))
+    }
+}
+
+impl From<String> for Href {
+    fn from(href: String) -> Self {
+        Self(AttrValue::from(href))
+    }
+}
+
+impl From<AttrValue> for Href {
+    fn from(href: AttrValue) -> Self {
+        Self(href)
+    }
+}
+
+impl From<Href> for AttrValue {
+    fn from(href: Href) -> Self {
+        href.0
+    }
+}
+
+impl From<Url> for Href {
+    /// Builds the url, falling back to its unfilled pattern if a param is missing.
+    ///
+    /// Use [`Url::build`] to handle the error instead.
+    fn from(url: Url) -> Self {
+        url.build().unwrap_or_else(|err| {
+            if cfg!(target_arch = "wasm32") {
+                log(&format!("Failed to build url {:?}: {}", url.path, err).into());
+            }
+            Href::from(url.path.clone())
+        })
+    }
+}
+
+impl IntoPropValue<Href> for &'static str {
+    fn into_prop_value(self) -> Href {
+        self.into()
+    }
+}
+
+impl IntoPropValue<Href> for String {
+    fn into_prop_value(self) -> Href {
+        self.into()
+    }
+}
+
+impl IntoPropValue<Href> for Url {
+    fn into_prop_value(self) -> Href {
+        self.into()
+    }
+}
+
+/// A builder for route hrefs.
+///
+/// The route pattern may contain dynamic segments written as `[name]` or `:name`, catch-all
+/// segments written as `[...name]` and optional catch-all segments written as `[[...name]]`.
+/// Params are percent-encoded, so they can safely contain spaces, slashes or `?`.
+///
+/// Queries are serialized from any `Serialize` value with sorted keys, so the same query always
+/// produces the same href. Nested objects use bracketed keys, scalar arrays repeat their key and
+/// `null` values are skipped.
+///
+/// # Example
+/// ```
+/// use next_rs::router::Url;
+/// use serde_json::json;
+///
+/// let href = Url::new("/posts/[id]/[...path]")
+///     .param("id", 42)
+///     .param("path", "a b/c")
+///     .query(&json!({"sort": "date", "filter": {"tag": "rust"}}))
+///     .hash("comments")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(
+///     href.as_str(),
+///     "/posts/42/a%20b/c?filter%5Btag%5D=rust&sort=date#comments"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Url {
+    /// The route pattern.
+    path: String,
+
+    /// Params filling the dynamic segments of the pattern.
+    params: Vec<(String, String)>,
+
+    /// Encoded query carried over from the pattern.
+    raw_query: String,
+
+    /// Query key/value pairs, encoded when building.
+    query: Vec<(String, String)>,
+
+    /// Encoded hash fragment, without the leading `#`.
+    hash: String,
+
+    /// The first error raised while configuring the builder.
+    error: Option<UrlError>,
+}
+
+impl Url {
+    /// Creates a builder for the given route pattern.
+    ///
+    /// A query string or hash fragment already present in the pattern is kept as-is.
+    pub fn new(pattern: &str) -> Self {
+        let (rest, hash) = pattern.split_once('#').unwrap_or((pattern, ""));
+        let (path, raw_query) = rest.split_once('?').unwrap_or((rest, ""));
+
+        Self {
+            path: path.to_string(),
+            raw_query: raw_query.to_string(),
+            hash: hash.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the value of a dynamic segment.
+    pub fn param(mut self, name: &str, value: impl ToString) -> Self {
+        self.params.retain(|(key, _)| key != name);
+        self.params.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Appends the fields of `query` to the query string.
+    ///
+    /// The query must serialize to a map, such as a struct or a JSON object.
+    pub fn query<Q: Serialize + ?Sized>(mut self, query: &Q) -> Self {
+        match serde_json::to_value(query) {
+            Ok(value @ (Value::Object(_) | Value::Null)) => {
+                flatten_query("", &value, &mut self.query)
+            }
+            Ok(_) => {
+                self.error.get_or_insert(UrlError::InvalidQuery(
+                    "the query must serialize to a map".to_string(),
+                ));
+            }
+            Err(err) => {
+                self.error
+                    .get_or_insert(UrlError::InvalidQuery(err.to_string()));
+            }
+        }
+        self
+    }
+
+    /// Appends a single key/value pair to the query string.
+    pub fn query_pair(mut self, key: &str, value: impl ToString) -> Self {
+        self.query.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sets the hash fragment, with or without its leading `#`.
+    pub fn hash(mut self, hash: &str) -> Self {
+        self.hash = percent_encode(hash.trim_start_matches('#'), FRAGMENT_SAFE);
+        self
+    }
+
+    /// Returns the value of a param, if set.
+    fn param_value(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Fills the route pattern with the params.
+    fn fill_path(&self) -> Result<String, UrlError> {
+        let mut segments = Vec::new();
+        for segment in self.path.split('/') {
+            if let Some(name) = segment
+                .strip_prefix("[[...")
+                .and_then(|s| s.strip_suffix("]]"))
+            {
+                if let Some(value) = self.param_value(name) {
+                    segments.extend(value.split('/').map(|v| percent_encode(v, UNRESERVED)));
+                }
+            } else if let Some(name) = segment
+                .strip_prefix("[...")
+                .and_then(|s| s.strip_suffix(']'))
+            {
+                let value = self
+                    .param_value(name)
+                    .ok_or_else(|| UrlError::MissingParam(name.to_string()))?;
+                segments.extend(value.split('/').map(|v| percent_encode(v, UNRESERVED)));
+            } else if let Some(name) = segment
+                .strip_prefix('[')
+                .and_then(|s| s.strip_suffix(']'))
+                .or_else(|| segment.strip_prefix(':'))
+            {
+                let value = self
+                    .param_value(name)
+                    .ok_or_else(|| UrlError::MissingParam(name.to_string()))?;
+                segments.push(percent_encode(value, UNRESERVED));
+            } else {
+                segments.push(segment.to_string());
+            }
+        }
+
+        let path = segments.join("/");
+        if path.is_empty() && self.path.starts_with('/') {
+            Ok("/".to_string())
+        } else {
+            Ok(path)
+        }
+    }
+
+    /// Builds the href, relative to the router's basename.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the [`Href`] on success and a [`UrlError`] if a param is missing or
+    /// the query could not be serialized.
+    pub fn build(&self) -> Result<Href, UrlError> {
+        if let Some(err) = &self.error {
+            return Err(err.clone());
+        }
+
+        let mut href = self.fill_path()?;
+
+        let mut query: Vec<String> = Vec::new();
+        if !self.raw_query.is_empty() {
+            query.push(self.raw_query.clone());
+        }
+        query.extend(self.query.iter().map(|(key, value)| {
+            format!(
+                "{}={}",
+                percent_encode(key, UNRESERVED),
+                percent_encode(value, UNRESERVED)
+            )
+        }));
+        if !query.is_empty() {
+            href.push('?');
+            href.push_str(&query.join("&"));
+        }
+
+        if !self.hash.is_empty() {
+            href.push('#');
+            href.push_str(&self.hash);
+        }
+
+        Ok(href.into())
+    }
+
+    /// Builds the href prefixed with `basename`, for use outside of the router.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the full href on success and a [`UrlError`] otherwise.
+    pub fn build_with_basename(&self, basename: &str) -> Result<String, UrlError> {
+        let href = self.build()?;
+        let basename = basename.strip_suffix('/').unwrap_or(basename);
+        if href.starts_with('/') {
+            Ok(format!("{}{}", basename, href))
+        } else {
+            Ok(href.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn url_errors_keep_their_message_as_navigation_errors() {
+        let err = NavigationError::from(UrlError::MissingParam("slug".into()));
+        let NavigationError::QuerySer(err) = err else {
+            panic!("expected a query serialization error, got {:?}", err);
+        };
+        assert_eq!(err.to_string(), "missing route param `slug`");
+    }
+
+    #[test]
+    fn state_navigation_keeps_the_location_state() {
+        use crate::history::MemoryHistory;
+
+        let history = AnyHistory::Memory(MemoryHistory::with_entries(vec!["/"]));
+        let state = HistoryState::new(&json!({ "tab": "billing" }));
+        navigate_history(&history, "/settings".into(), false, state.clone());
+
+        let location = history.location();
+        assert_eq!(location.path(), "/settings");
+        assert_eq!(location.state::<HistoryState>().as_deref(), Some(&state));
+
+        let state = HistoryState::new(&json!({ "tab": "team" }));
+        navigate_history(&history, "/settings".into(), true, state.clone());
+        let location = history.location();
+        assert_eq!(location.state::<HistoryState>().as_deref(), Some(&state));
+        assert_eq!(history.len(), 2);
+
+        history.back();
+        let location = history.location();
+        assert_eq!(location.path(), "/");
+        assert_eq!(location.state::<HistoryState>(), None);
+    }
+
+    #[test]
+    fn history_state_round_trips_through_json() {
+        let json = HistoryState::new(&json!({ "tab": "billing" })).to_json();
+        let state = HistoryState::from_json(&json).unwrap();
+        assert_eq!(state.version, HISTORY_STATE_VERSION);
+        assert_eq!(state.value::<Value>(), Some(json!({ "tab": "billing" })));
+    }
+
+    #[test]
+    fn history_state_of_another_version_is_ignored() {
+        let stored = HistoryState {
+            version: HISTORY_STATE_VERSION + 1,
+            value: json!("billing"),
+        };
+        let state = HistoryState::from_json(&stored.to_json()).unwrap();
+        assert_eq!(state, stored);
+        assert_eq!(state.value::<String>(), None);
+    }
+
+    #[test]
+    fn foreign_history_state_is_not_read() {
+        assert_eq!(HistoryState::from_json(r#"{"__id":3}"#), None);
+        assert_eq!(HistoryState::from_json("not json"), None);
+    }
+
+    #[test]
+    fn percent_decoding_leaves_invalid_sequences() {
+        assert_eq!(percent_decode("a%20b%2Fc"), "a b/c");
+        assert_eq!(percent_decode("caf%C3%A9"), "café");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%4"), "%zz%4");
+    }
+
+    #[test]
+    fn routes_match_dynamic_and_catch_all_segments() {
+        let params = match_route("/posts/:id", "/posts/hello%20world?page=2").unwrap();
+        assert_eq!(params["id"], "hello world");
+
+        assert!(match_route("/docs/[...slug]", "/docs").is_none());
+        let params = match_route("/docs/[...slug]", "/docs/a/b").unwrap();
+        assert_eq!(params["slug"], "a/b");
+
+        let params = match_route("/shop/[[...slug]]", "/shop").unwrap();
+        assert_eq!(params["slug"], "");
+
+        assert!(match_route("/posts/[id]", "/posts/1/comments").is_none());
+        assert!(match_route("/posts/[id]", "/posts").is_none());
+        assert!(match_route("/", "/").unwrap().is_empty());
+    }
+
+    #[test]
+    fn percent_encoding_keeps_only_safe_characters() {
+        assert_eq!(percent_encode("a b/c?d", UNRESERVED), "a%20b%2Fc%3Fd");
+        assert_eq!(
+            percent_encode("café-1_2.3~", UNRESERVED),
+            "caf%C3%A9-1_2.3~"
+        );
+        assert_eq!(
+            percent_encode("top/section?x", FRAGMENT_SAFE),
+            "top/section?x"
+        );
+    }
+
+    #[test]
+    fn nested_queries_are_flattened_with_sorted_keys() {
+        let mut pairs = Vec::new();
+        let query = json!({
+            "tag": ["a", "b"],
+            "filter": { "year": 2024, "draft": false },
+            "items": [{ "id": 1 }],
+            "empty": null,
+        });
+        flatten_query("", &query, &mut pairs);
+        let pairs: Vec<(&str, &str)> = pairs
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                ("filter[draft]", "false"),
+                ("filter[year]", "2024"),
+                ("items[0][id]", "1"),
+                ("tag", "a"),
+                ("tag", "b"),
+            ]
+        );
+    }
+
+    #[test]
+    fn urls_are_joined_with_the_basename() {
+        let url = Url::new("/blog/[slug]").param("slug", "hello world");
+        assert_eq!(
+            url.build_with_basename("/docs/").unwrap(),
+            "/docs/blog/hello%20world"
+        );
+        assert_eq!(url.build_with_basename("").unwrap(), "/blog/hello%20world");
+        assert_eq!(
+            Url::new("#top").build_with_basename("/docs").unwrap(),
+            "#top"
+        );
+        assert_eq!(
+            Url::new("/blog/[slug]").build_with_basename("/docs"),
+            Err(UrlError::MissingParam("slug".to_string()))
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use crate::csp::use_nonce;
+use crate::head::{Head, SSR_ATTRIBUTE};
+use crate::log;
+use crate::prelude::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use web_sys::wasm_bindgen::closure::Closure;
+use web_sys::wasm_bindgen::JsCast;
+use web_sys::{window, HtmlScriptElement, Node};
+
+thread_local! {
+    // Scripts added by `Script` components, keyed by id, src or inline content, so each one is
+    // only added once across navigations.
+    static SCRIPTS: RefCell<HashMap<String, ScriptStatus>> = RefCell::new(HashMap::new());
+}
+
+/// The loading status of a script.
+#[derive(Clone, PartialEq)]
+enum ScriptStatus {
+    Loading(HtmlScriptElement),
+    Loaded,
+    Failed(String),
+}
+
+/// Properties for the Script component.
+#[derive(Properties, Clone, PartialEq)]
+pub struct ScriptProps {
+    /// The URL of an external script.
+    #[prop_or_default]
+    pub src: &'static str,
+
+    /// The id of the script, used to deduplicate it. Required for inline scripts rendered with
+    /// different content.
+    #[prop_or_default]
+    pub id: &'static str,
+
+    /// The code of an inline script, used when `src` is empty.
+    #[prop_or_default]
+    pub content: AttrValue,
+
+    /// When to load the script. Valid values:
+    /// - "before_interactive": as soon as the component renders, in `<head>`, in order;
+    /// - "after_interactive": once the component is mounted;
+    /// - "lazy_onload": once the page has loaded;
+    /// - "worker": handed to a web worker library such as Partytown, as a `text/partytown` script.
+    #[prop_or("after_interactive")]
+    pub strategy: &'static str,
+
+    /// The CSP nonce allowing the script to run. Defaults to the nonce of the `CspProvider`.
+    #[prop_or_default]
+    pub nonce: &'static str,
+
+    /// Callback called once, when the script has loaded.
+    #[prop_or_default]
+    pub on_load: Callback<()>,
+
+    /// Callback called when the script fails to load.
+    #[prop_or_default]
+    pub on_error: Callback<String>,
+
+    /// Callback called when the script has loaded, and on every later mount of the component.
+    #[prop_or_default]
+    pub on_ready: Callback<()>,
+}
+
+/// Returns the key deduplicating a script: its id, src or inline content.
+fn script_key(props: &ScriptProps) -> String {
+    [props.id, props.src]
+        .into_iter()
+        .find(|key| !key.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| props.content.to_string())
+}
+
+/// Sets the loading status of the script with the given key.
+fn set_status(key: &str, status: ScriptStatus) {
+    SCRIPTS.with(|scripts| scripts.borrow_mut().insert(key.to_string(), status));
+}
+
+/// Calls the callbacks of `props` when `script` loads or fails to load.
+fn listen(script: &HtmlScriptElement, props: &ScriptProps) {
+    let on_load = {
+        let key = script_key(props);
+        let props = props.clone();
+        Closure::once_into_js(move || {
+            set_status(&key, ScriptStatus::Loaded);
+            props.on_load.emit(());
+            props.on_ready.emit(());
+        })
+    };
+    let on_error = {
+        let key = script_key(props);
+        let props = props.clone();
+        Closure::once_into_js(move || {
+            let error = format!("Failed to load script {}", key);
+            set_status(&key, ScriptStatus::Failed(error.clone()));
+            props.on_error.emit(error);
+        })
+    };
+
+    let _ = script.add_event_listener_with_callback("load", on_load.unchecked_ref());
+    let _ = script.add_event_listener_with_callback("error", on_error.unchecked_ref());
+}
+
+/// Adds the script described by `props` to the document, unless it was already added.
+fn load_script(props: &ScriptProps, nonce: &str) {
+    // Early scripts load while rendering, which also happens on the server.
+    if !cfg!(target_arch = "wasm32") {
+        return;
+    }
+    let key = script_key(props);
+    match SCRIPTS.with(|scripts| scripts.borrow().get(&key).cloned()) {
+        Some(ScriptStatus::Loaded) => {
+            props.on_ready.emit(());
+            return;
+        }
+        Some(ScriptStatus::Loading(script)) => {
+            listen(&script, props);
+            return;
+        }
+        Some(ScriptStatus::Failed(error)) => {
+            props.on_error.emit(error);
+            return;
+        }
+        None => {}
+    }
+
+    let document = match window().and_then(|win| win.document()) {
+        Some(document) => document,
+        None => return,
+    };
+
+    // Early scripts rendered by the server already ran while the page was parsed.
+    let rendered = document
+        .query_selector_all(&format!("script[{}]", SSR_ATTRIBUTE))
+        .ok();
+    let server_rendered = rendered.is_some_and(|rendered| {
+        (0..rendered.length())
+            .filter_map(|index| rendered.get(index))
+            .filter_map(|node| node.dyn_into::<HtmlScriptElement>().ok())
+            .any(|script| match (props.id, props.src) {
+                ("", "") => script.text().ok().as_deref() == Some(props.content.as_str()),
+                ("", src) => script.get_attribute("src").as_deref() == Some(src),
+                (id, _) => script.id() == id,
+            })
+    });
+    if server_rendered {
+        set_status(&key, ScriptStatus::Loaded);
+        props.on_load.emit(());
+        props.on_ready.emit(());
+        return;
+    }
+    let script = match document
+        .create_element("script")
+        .ok()
+        .and_then(|element| element.dyn_into::<HtmlScriptElement>().ok())
+    {
+        Some(script) => script,
+        None => {
+            log(&"Failed to create script element".into());
+            return;
+        }
+    };
+
+    if !props.id.is_empty() {
+        script.set_id(props.id);
+    }
+    if !nonce.is_empty() {
+        let _ = script.set_attribute("nonce", nonce);
+    }
+    if props.strategy == "worker" {
+        script.set_type("text/partytown");
+    }
+    // Scripts added from code are async by default, so keep early scripts in order.
+    script.set_async(props.strategy != "before_interactive");
+
+    let inline = props.src.is_empty();
+    if inline {
+        script.set_text(&props.content).ok();
+    } else {
+        listen(&script, props);
+        script.set_src(props.src);
+    }
+
+    let parent: Option<Node> = match props.strategy {
+        "before_interactive" => document.head().map(Into::into),
+        _ => document.body().map(Into::into),
+    };
+    if let Some(parent) = parent {
+        if let Err(err) = parent.append_child(&script) {
+            log(&format!("Error adding script {}: {:?}", key, err).into());
+            return;
+        }
+    }
+
+    if inline {
+        // Inline scripts run as soon as they are added.
+        set_status(&key, ScriptStatus::Loaded);
+        if props.strategy != "worker" {
+            props.on_load.emit(());
+            props.on_ready.emit(());
+        }
+    } else {
+        set_status(&key, ScriptStatus::Loading(script));
+    }
+}
+
+/// Loads a third-party or inline script with a loading strategy.
+///
+/// Scripts are deduplicated by `id`, then `src`, across navigations: a script is only added once,
+/// and later mounts of the component only call `on_ready`. Scripts stay in the document when the
+/// component unmounts.
+///
+/// On the server, `before_interactive` scripts are rendered into `<head>` by the head manager, and
+/// the client doesn't add them again.
+///
+/// # Arguments
+/// * `props` - The properties of the component.
+///
+/// # Returns
+/// (Html): Nothing on the client, the script is added to the document directly.
+///
+/// # Examples
+/// ```
+/// use next_rs::prelude::*;
+/// use next_rs::{log, Script};
+///
+/// #[func]
+/// pub fn Analytics() -> Html {
+///     rsx! {
+///         <>
+///             <Script
+///                 src="https://www.googletagmanager.com/gtag/js?id=G-XXXX"
+///                 strategy="lazy_onload"
+///                 on_load={Callback::from(|_| log(&"gtag loaded".into()))}
+///             />
+///             <Script id="gtag-init" content="window.dataLayer = window.dataLayer || [];" />
+///         </>
+///     }
+/// }
+/// ```
+#[func]
+pub fn Script(props: &ScriptProps) -> Html {
+    let context_nonce = use_nonce();
+    let nonce: AttrValue = match props.nonce.is_empty() {
+        true => context_nonce.unwrap_or_default(),
+        false => props.nonce.into(),
+    };
+
+    // Early scripts are added while rendering, before any effect runs.
+    {
+        let props = props.clone();
+        let nonce = nonce.clone();
+        use_state(move || {
+            if props.strategy == "before_interactive" {
+                load_script(&props, &nonce);
+            }
+        });
+    }
+
+    {
+        let props = props.clone();
+        let nonce = nonce.clone();
+        use_effect_with((), move |_| match props.strategy {
+            "before_interactive" => {}
+            "lazy_onload" => {
+                let complete = window()
+                    .and_then(|win| win.document())
+                    .is_none_or(|doc| doc.ready_state() == "complete");
+                if complete {
+                    load_script(&props, &nonce);
+                } else if let Some(win) = window() {
+                    let on_load = Closure::once_into_js(move || load_script(&props, &nonce));
+                    let _ = win.add_event_listener_with_callback("load", on_load.unchecked_ref());
+                }
+            }
+            _ => load_script(&props, &nonce),
+        });
+    }
+
+    // Early scripts are rendered into `<head>` by the server. The client keeps an empty `Head` in
+    // their place, so that hydrated pages have the same tree.
+    if props.strategy == "before_interactive" {
+        let script = match cfg!(target_arch = "wasm32") {
+            true => Html::default(),
+            false => rsx! {
+                <script
+                    id={(!props.id.is_empty()).then_some(props.id)}
+                    src={(!props.src.is_empty()).then_some(props.src)}
+                    nonce={(!nonce.is_empty()).then_some(nonce)}
+                >
+                    {props.content.clone()}
+                </script>
+            },
+        };
+        return rsx! { <Head>{script}</Head> };
+    }
+
+    Html::default()
+}
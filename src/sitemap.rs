@@ -0,0 +1,484 @@
+use crate::alternates::locale_alternates;
+use crate::route_meta::Route;
+use crate::router::{RouteParams, Url, UrlError};
+
+/// The maximum number of URLs of a single sitemap file. Larger sitemaps are split into several
+/// files listed by a sitemap index.
+pub const MAX_URLS: usize = 50_000;
+
+/// How often the content of a page changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeFreq {
+    Always,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+    Never,
+}
+
+impl ChangeFreq {
+    /// Returns the value of the `<changefreq>` element.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeFreq::Always => "always",
+            ChangeFreq::Hourly => "hourly",
+            ChangeFreq::Daily => "daily",
+            ChangeFreq::Weekly => "weekly",
+            ChangeFreq::Monthly => "monthly",
+            ChangeFreq::Yearly => "yearly",
+            ChangeFreq::Never => "never",
+        }
+    }
+}
+
+/// A page listed in the sitemap.
+///
+/// The path may be a route pattern, such as `/posts/[id]`, filled with [`SitemapEntry::param`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SitemapEntry {
+    /// The path of the page or its route pattern, without the basename.
+    pub path: String,
+    /// Params filling the dynamic segments of the path.
+    pub params: Vec<(String, String)>,
+    /// The date of the last modification, such as `2024-05-01`.
+    pub lastmod: Option<String>,
+    /// How often the page changes.
+    pub changefreq: Option<ChangeFreq>,
+    /// The priority of the page relative to the other pages of the site, from `0.0` to `1.0`.
+    pub priority: Option<f32>,
+    /// The localized versions of the page, as `(hreflang, path)` pairs.
+    pub alternates: Vec<(String, String)>,
+}
+
+impl SitemapEntry {
+    /// Creates an entry for a path or route pattern.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the value of a dynamic segment of the path.
+    pub fn param(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.params.push((name.into(), value.to_string()));
+        self
+    }
+
+    /// Sets the date of the last modification.
+    pub fn lastmod(mut self, lastmod: impl Into<String>) -> Self {
+        self.lastmod = Some(lastmod.into());
+        self
+    }
+
+    /// Sets how often the page changes.
+    pub fn changefreq(mut self, changefreq: ChangeFreq) -> Self {
+        self.changefreq = Some(changefreq);
+        self
+    }
+
+    /// Sets the priority of the page, clamped between `0.0` and `1.0`.
+    pub fn priority(mut self, priority: f32) -> Self {
+        self.priority = Some(priority.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Adds a localized version of the page.
+    pub fn alternate(mut self, hreflang: impl Into<String>, path: impl Into<String>) -> Self {
+        self.alternates.push((hreflang.into(), path.into()));
+        self
+    }
+
+    /// Returns the path with its params filled in.
+    fn filled_path(&self) -> Result<String, UrlError> {
+        let url = self
+            .params
+            .iter()
+            .fold(Url::new(&self.path), |url, (name, value)| {
+                url.param(name, value)
+            });
+        Ok(url.build()?.as_str().to_string())
+    }
+}
+
+/// Returns whether a route pattern has dynamic segments.
+fn is_dynamic(pattern: &str) -> bool {
+    pattern
+        .split('/')
+        .any(|segment| segment.starts_with('[') || segment.starts_with(':'))
+}
+
+/// Escapes the characters XML reserves.
+pub(crate) fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// A sitemap of the site, built from its route table.
+///
+/// Static routes are listed as is, while dynamic routes are listed through generator functions
+/// returning an entry per page. Routes whose metadata sets `noindex` are left out.
+///
+/// # Example
+/// ```
+/// use next_rs::route_meta::Route;
+/// use next_rs::sitemap::{ChangeFreq, Sitemap, SitemapEntry};
+///
+/// let routes = vec![Route::new("/"), Route::new("/pricing"), Route::new("/posts/[id]")];
+/// let posts = vec![(1, "2024-05-01"), (2, "2024-05-08")];
+///
+/// let sitemap = Sitemap::new("https://acme.dev")
+///     .routes(&routes)
+///     .dynamic("/posts/[id]", || {
+///         posts.iter().map(|(id, date)| {
+///             SitemapEntry::default()
+///                 .param("id", id)
+///                 .lastmod(*date)
+///                 .changefreq(ChangeFreq::Monthly)
+///         })
+///     });
+///
+/// let files = sitemap.to_files().unwrap();
+/// assert_eq!(files.len(), 1);
+/// assert!(files[0].1.contains("<loc>https://acme.dev/posts/2</loc>"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Sitemap {
+    /// The origin of the site, such as `https://acme.dev`.
+    pub origin: String,
+    /// The basename the site is served under, such as `/docs`.
+    pub basename: String,
+    /// The locales of the site, every page being listed with its alternates in each of them.
+    pub locales: Vec<String>,
+    /// The locale served without a prefix.
+    pub default_locale: String,
+    /// The pages of the site.
+    pub entries: Vec<SitemapEntry>,
+}
+
+impl Sitemap {
+    /// Creates an empty sitemap for a site.
+    pub fn new(origin: impl Into<String>) -> Self {
+        Self {
+            origin: origin.into().trim_end_matches('/').to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the basename the site is served under.
+    pub fn basename(mut self, basename: impl Into<String>) -> Self {
+        self.basename = basename.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Lists every page in each locale, the default locale being served without a prefix.
+    pub fn locales<L: ToString>(mut self, locales: &[L], default_locale: &str) -> Self {
+        self.locales = locales.iter().map(ToString::to_string).collect();
+        self.default_locale = default_locale.to_string();
+        self
+    }
+
+    /// Adds the static routes of a route table, skipping dynamic ones and those set to
+    /// `noindex`.
+    pub fn routes(mut self, routes: &[Route]) -> Self {
+        for route in routes.iter().filter(|route| !is_dynamic(route.path)) {
+            let robots = route.resolve_meta(RouteParams::new()).robots;
+            if robots.is_some_and(|robots| robots.contains("noindex")) {
+                continue;
+            }
+            self.entries.push(SitemapEntry::new(route.path));
+        }
+        self
+    }
+
+    /// Adds the pages of a dynamic route, as generated by `generator`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The route pattern, such as `/posts/[id]`.
+    /// * `generator` - Returns an entry per page, with the params of the pattern. Entries without a
+    ///   path take the pattern.
+    pub fn dynamic<F, I>(mut self, pattern: &str, generator: F) -> Self
+    where
+        F: FnOnce() -> I,
+        I: IntoIterator<Item = SitemapEntry>,
+    {
+        self.entries.extend(
+            generator()
+                .into_iter()
+                .map(|entry| match entry.path.is_empty() {
+                    true => SitemapEntry {
+                        path: pattern.to_string(),
+                        ..entry
+                    },
+                    false => entry,
+                }),
+        );
+        self
+    }
+
+    /// Adds a single page.
+    pub fn entry(mut self, entry: SitemapEntry) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    /// Returns the absolute URL of a path.
+    fn url(&self, path: &str) -> String {
+        format!("{}{}{}", self.origin, self.basename, path)
+    }
+
+    /// Renders the `<url>` element of every page.
+    fn urls(&self) -> Result<Vec<String>, UrlError> {
+        let mut urls = Vec::new();
+        for entry in &self.entries {
+            let path = entry.filled_path()?;
+            let mut alternates: Vec<(String, String)> = entry
+                .alternates
+                .iter()
+                .map(|(hreflang, path)| (hreflang.clone(), self.url(path)))
+                .collect();
+            let localized = locale_alternates(&path, &self.locales, &self.default_locale, |path| {
+                self.url(path)
+            });
+
+            // Each localized version is a page of its own, listing the same alternates.
+            let mut locs: Vec<String> = localized
+                .iter()
+                .filter(|alternate| alternate.hreflang.as_deref() != Some("x-default"))
+                .map(|alternate| alternate.href.to_string())
+                .collect();
+            if locs.is_empty() {
+                locs.push(self.url(&path));
+            }
+            alternates.extend(localized.into_iter().filter_map(|alternate| {
+                Some((alternate.hreflang?.to_string(), alternate.href.to_string()))
+            }));
+
+            for loc in locs {
+                let mut url = format!("  <url>\n    <loc>{}</loc>\n", escape_xml(&loc));
+                if let Some(lastmod) = &entry.lastmod {
+                    url.push_str(&format!("    <lastmod>{}</lastmod>\n", escape_xml(lastmod)));
+                }
+                if let Some(changefreq) = entry.changefreq {
+                    url.push_str(&format!(
+                        "    <changefreq>{}</changefreq>\n",
+                        changefreq.as_str()
+                    ));
+                }
+                if let Some(priority) = entry.priority {
+                    url.push_str(&format!("    <priority>{:.1}</priority>\n", priority));
+                }
+                for (hreflang, href) in &alternates {
+                    url.push_str(&format!(
+                        "    <xhtml:link rel=\"alternate\" hreflang=\"{}\" href=\"{}\" />\n",
+                        escape_xml(hreflang),
+                        escape_xml(href)
+                    ));
+                }
+                url.push_str("  </url>\n");
+                urls.push(url);
+            }
+        }
+        Ok(urls)
+    }
+
+    /// Renders the sitemap files.
+    ///
+    /// Up to [`MAX_URLS`] URLs, a single `sitemap.xml` is produced. Beyond, the URLs are split
+    /// into `sitemap-1.xml`, `sitemap-2.xml` and so on, and `sitemap.xml` is the index listing
+    /// them.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `(file name, content)` of every file on success, and a
+    /// [`UrlError`] if a param of a dynamic route is missing.
+    pub fn to_files(&self) -> Result<Vec<(String, String)>, UrlError> {
+        self.files(MAX_URLS)
+    }
+
+    /// Renders the sitemap files, with up to `max_urls` URLs per file.
+    fn files(&self, max_urls: usize) -> Result<Vec<(String, String)>, UrlError> {
+        let urls = self.urls()?;
+        let urlset = |urls: &[String]| {
+            format!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+                 <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\" \
+                 xmlns:xhtml=\"http://www.w3.org/1999/xhtml\">\n{}</urlset>\n",
+                urls.concat()
+            )
+        };
+
+        if urls.len() <= max_urls {
+            return Ok(vec![("sitemap.xml".to_string(), urlset(&urls))]);
+        }
+
+        let mut files: Vec<(String, String)> = urls
+            .chunks(max_urls)
+            .enumerate()
+            .map(|(index, urls)| (format!("sitemap-{}.xml", index + 1), urlset(urls)))
+            .collect();
+        let sitemaps: String = files
+            .iter()
+            .map(|(name, _)| {
+                format!(
+                    "  <sitemap>\n    <loc>{}</loc>\n  </sitemap>\n",
+                    escape_xml(&self.url(&format!("/{}", name)))
+                )
+            })
+            .collect();
+        files.insert(
+            0,
+            (
+                "sitemap.xml".to_string(),
+                format!(
+                    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+                     <sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n\
+                     {}</sitemapindex>\n",
+                    sitemaps
+                ),
+            ),
+        );
+        Ok(files)
+    }
+
+    /// Writes the sitemap files into `dir`, such as the dist directory, from a build script or a
+    /// native build step.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the paths of the written files on success.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn write_to(
+        &self,
+        dir: impl AsRef<std::path::Path>,
+    ) -> std::io::Result<Vec<std::path::PathBuf>> {
+        let files = self
+            .to_files()
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        std::fs::create_dir_all(dir.as_ref())?;
+        let mut paths = Vec::new();
+        for (name, content) in files {
+            let path = dir.as_ref().join(name);
+            std::fs::write(&path, content)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::route_meta::RouteMeta;
+
+    fn locs(content: &str) -> Vec<&str> {
+        content
+            .split("<loc>")
+            .skip(1)
+            .filter_map(|rest| rest.split("</loc>").next())
+            .collect()
+    }
+
+    #[test]
+    fn large_sitemaps_are_split_behind_an_index() {
+        let sitemap = Sitemap::new("https://acme.dev/")
+            .basename("/docs")
+            .entry(SitemapEntry::new("/"))
+            .entry(SitemapEntry::new("/pricing"))
+            .entry(SitemapEntry::new("/blog"));
+
+        let files = sitemap.files(2).unwrap();
+        let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["sitemap.xml", "sitemap-1.xml", "sitemap-2.xml"]);
+
+        assert!(files[0].1.contains("<sitemapindex"));
+        assert_eq!(
+            locs(&files[0].1),
+            [
+                "https://acme.dev/docs/sitemap-1.xml",
+                "https://acme.dev/docs/sitemap-2.xml"
+            ]
+        );
+        assert_eq!(
+            locs(&files[1].1),
+            ["https://acme.dev/docs/", "https://acme.dev/docs/pricing"]
+        );
+        assert_eq!(locs(&files[2].1), ["https://acme.dev/docs/blog"]);
+
+        let files = sitemap.files(3).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].1.contains("<urlset"));
+    }
+
+    #[test]
+    fn localized_pages_list_their_alternates_and_x_default() {
+        let files = Sitemap::new("https://acme.dev")
+            .locales(&["en", "fr"], "en")
+            .entry(
+                SitemapEntry::new("/pricing")
+                    .lastmod("2024-05-01")
+                    .changefreq(ChangeFreq::Weekly)
+                    .priority(0.8),
+            )
+            .to_files()
+            .unwrap();
+
+        let alternates = "    <xhtml:link rel=\"alternate\" hreflang=\"en\" href=\"https://acme.dev/pricing\" />\n    \
+             <xhtml:link rel=\"alternate\" hreflang=\"fr\" href=\"https://acme.dev/fr/pricing\" />\n    \
+             <xhtml:link rel=\"alternate\" hreflang=\"x-default\" href=\"https://acme.dev/pricing\" />\n";
+        let url = |loc: &str| {
+            format!(
+                "  <url>\n    <loc>{}</loc>\n    <lastmod>2024-05-01</lastmod>\n    \
+                 <changefreq>weekly</changefreq>\n    <priority>0.8</priority>\n{}  </url>\n",
+                loc, alternates
+            )
+        };
+        assert_eq!(files.len(), 1);
+        assert!(files[0].1.contains(&format!(
+            "{}{}",
+            url("https://acme.dev/pricing"),
+            url("https://acme.dev/fr/pricing")
+        )));
+    }
+
+    #[test]
+    fn noindex_and_dynamic_routes_are_left_out() {
+        let routes = vec![
+            Route::new("/"),
+            Route::new("/admin").meta(RouteMeta::new().robots("noindex, nofollow")),
+            Route::new("/posts/[id]"),
+        ];
+        let files = Sitemap::new("https://acme.dev")
+            .routes(&routes)
+            .dynamic("/posts/[id]", || {
+                [1, 2].map(|id| SitemapEntry::default().param("id", id))
+            })
+            .to_files()
+            .unwrap();
+
+        assert_eq!(
+            locs(&files[0].1),
+            [
+                "https://acme.dev/",
+                "https://acme.dev/posts/1",
+                "https://acme.dev/posts/2"
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_params_are_url_errors() {
+        let result = Sitemap::new("https://acme.dev")
+            .dynamic("/posts/[id]", || [SitemapEntry::default()])
+            .to_files();
+
+        assert_eq!(result, Err(UrlError::MissingParam("id".to_string())));
+    }
+}
@@ -0,0 +1,87 @@
+use crate::head::Head;
+use crate::prelude::*;
+
+/// Properties for the Stylesheet component.
+#[derive(Properties, Clone, PartialEq)]
+pub struct StylesheetProps {
+    /// The URL of an external stylesheet.
+    #[prop_or_default]
+    pub href: AttrValue,
+
+    /// The CSS of an inline stylesheet, used when `href` is empty.
+    #[prop_or_default]
+    pub content: AttrValue,
+
+    /// The id of an inline stylesheet, used to deduplicate it across pages.
+    #[prop_or_default]
+    pub id: AttrValue,
+
+    /// The media query the stylesheet applies to, such as `print` or `(min-width: 768px)`.
+    #[prop_or_default]
+    pub media: AttrValue,
+
+    /// The precedence of the stylesheet. Stylesheets of lower precedence come first in `<head>`,
+    /// so those of higher precedence override them.
+    #[prop_or_default]
+    pub precedence: i32,
+}
+
+/// Adds a stylesheet to `<head>` while mounted.
+///
+/// Stylesheets are rendered after the other head elements, ordered by precedence, then by mount,
+/// whatever the navigation history. A stylesheet used by several pages is only added once, by
+/// `href` or by `id`, and is removed once the last page using it unmounts, so the CSS of a route
+/// doesn't leak into the next one.
+///
+/// # Arguments
+/// * `props` - The properties of the component.
+///
+/// # Returns
+/// (Html): Nothing, the stylesheet is rendered by the head manager.
+///
+/// # Examples
+/// ```
+/// use next_rs::prelude::*;
+/// use next_rs::Stylesheet;
+///
+/// #[func]
+/// pub fn BlogPost() -> Html {
+///     rsx! {
+///         <>
+///             <Stylesheet href="/css/base.css" />
+///             <Stylesheet href="/css/blog.css" precedence={1} />
+///             <Stylesheet href="/css/print.css" media="print" precedence={2} />
+///             <Stylesheet id="post-accent" content=".post { color: teal; }" precedence={1} />
+///             <article class="post">{"Hello"}</article>
+///         </>
+///     }
+/// }
+/// ```
+#[func]
+pub fn Stylesheet(props: &StylesheetProps) -> Html {
+    let media = (!props.media.is_empty()).then(|| props.media.clone());
+    let precedence = props.precedence.to_string();
+
+    let stylesheet = match props.href.is_empty() {
+        false => rsx! {
+            <link
+                rel="stylesheet"
+                href={props.href.clone()}
+                media={media}
+                data-precedence={precedence}
+            />
+        },
+        true => {
+            let id = (!props.id.is_empty()).then(|| props.id.clone());
+            rsx! {
+                <style media={media} data-style-id={id} data-precedence={precedence}>
+                    {props.content.clone()}
+                </style>
+            }
+        }
+    };
+
+    rsx! {
+        <Head>{stylesheet}</Head>
+    }
+}
@@ -0,0 +1,228 @@
+use crate::head::{text_content, SSR_ATTRIBUTE, TAG_CLASS};
+use crate::prelude::*;
+use std::fmt;
+use web_sys::Element;
+
+/// A head element, normalized so that it can be compared in tests.
+///
+/// Attributes are sorted by name, the markers added by the head manager are removed, and
+/// whitespace in the text content is collapsed.
+///
+/// # Example
+/// ```
+/// use next_rs::prelude::*;
+/// use next_rs::head::map_components;
+/// use next_rs::testing::{snapshot, HeadEntry};
+///
+/// let head = map_components(vec![rsx! {
+///     <>
+///         <title>{"Old"}</title>
+///         <meta name="description" content="Next RS" />
+///         <title>{"  Home  "}</title>
+///     </>
+/// }]);
+/// let entries: Vec<HeadEntry> = head.iter().filter_map(HeadEntry::from_html).collect();
+///
+/// assert_eq!(
+///     snapshot(entries),
+///     "<meta charset=\"utf-8\">\n\
+///      <meta content=\"Next RS\" name=\"description\">\n\
+///      <title>Home</title>"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HeadEntry {
+    /// The name of the element, such as `meta`.
+    pub tag: String,
+    /// The attributes of the element, sorted by name.
+    pub attributes: Vec<(String, String)>,
+    /// The text content of the element, such as the title.
+    pub text: String,
+}
+
+impl HeadEntry {
+    /// Creates a normalized entry.
+    fn new(tag: &str, attributes: impl IntoIterator<Item = (String, String)>, text: &str) -> Self {
+        let mut attributes: Vec<(String, String)> = attributes
+            .into_iter()
+            .filter(|(name, _)| name != SSR_ATTRIBUTE)
+            .map(|(name, value)| match name.as_str() {
+                "class" => {
+                    let classes: Vec<&str> = value
+                        .split_whitespace()
+                        .filter(|class| *class != TAG_CLASS)
+                        .collect();
+                    (name, classes.join(" "))
+                }
+                _ => (name, value),
+            })
+            .filter(|(name, value)| name != "class" || !value.is_empty())
+            .collect();
+        attributes.sort();
+
+        Self {
+            tag: tag.to_lowercase(),
+            attributes,
+            text: text.split_whitespace().collect::<Vec<&str>>().join(" "),
+        }
+    }
+
+    /// Creates the entry of a head element.
+    ///
+    /// # Returns
+    ///
+    /// (Option<HeadEntry>): The entry, or `None` if `html` isn't an element, such as a component.
+    pub fn from_html(html: &Html) -> Option<Self> {
+        let tag = match html {
+            Html::VTag(tag) => tag,
+            _ => return None,
+        };
+        let attributes = tag
+            .attributes
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()));
+        let text = tag.children().map(text_content).unwrap_or_default();
+
+        Some(Self::new(tag.tag(), attributes, &text))
+    }
+
+    /// Creates the entry of an element of the document, such as one rendered by the server.
+    pub fn from_element(element: &Element) -> Self {
+        let names = element.get_attribute_names();
+        let attributes = names
+            .iter()
+            .filter_map(|name| name.as_string())
+            .map(|name| {
+                let value = element.get_attribute(&name).unwrap_or_default();
+                (name, value)
+            });
+        let text = element.text_content().unwrap_or_default();
+
+        Self::new(&element.tag_name(), attributes, &text)
+    }
+
+    /// Returns the value of an attribute.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl fmt::Display for HeadEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}", self.tag)?;
+        for (name, value) in &self.attributes {
+            write!(f, " {}=\"{}\"", name, value.replace('"', "&quot;"))?;
+        }
+        write!(f, ">")?;
+        if !self.text.is_empty() || matches!(self.tag.as_str(), "title" | "script" | "style") {
+            write!(f, "{}</{}>", self.text, self.tag)?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders head entries to a sorted string, one element per line, for snapshot assertions.
+///
+/// Sorting makes the snapshot independent of the order elements were rendered in.
+pub fn snapshot(entries: impl IntoIterator<Item = HeadEntry>) -> String {
+    let mut lines: Vec<String> = entries.into_iter().map(|entry| entry.to_string()).collect();
+    lines.sort();
+    lines.join("\n")
+}
+
+/// Props for [`SnapshotRoot`].
+#[cfg(feature = "ssr")]
+#[derive(Properties, Clone, PartialEq)]
+struct SnapshotRootProps {
+    collector: crate::head::HeadCollector,
+    children: Html,
+}
+
+/// Renders a tree below a head manager collecting its head elements.
+#[cfg(feature = "ssr")]
+#[func]
+fn SnapshotRoot(props: &SnapshotRootProps) -> Html {
+    rsx! {
+        <crate::head::HeadProvider collector={Some(props.collector.clone())}>
+            {props.children.clone()}
+        </crate::head::HeadProvider>
+    }
+}
+
+/// Renders a component tree without a browser and returns the entries of the elements its
+/// `Head` components contribute to `<head>`, resolved like on the client.
+///
+/// # Example
+/// ```rust,no_run
+/// use next_rs::prelude::*;
+/// use next_rs::Head;
+/// use next_rs::testing::head_entries;
+///
+/// #[func]
+/// fn Page() -> Html {
+///     rsx! {
+///         <Head>
+///             <title>{"Pricing"}</title>
+///         </Head>
+///     }
+/// }
+///
+/// async fn pricing_title() {
+///     let entries = head_entries(rsx! { <Page /> }).await;
+///     let title = entries.iter().find(|entry| entry.tag == "title").unwrap();
+///     assert_eq!(title.text, "Pricing");
+/// }
+/// ```
+#[cfg(feature = "ssr")]
+pub async fn head_entries(tree: Html) -> Vec<HeadEntry> {
+    let collector = crate::head::HeadCollector::new();
+    yew::LocalServerRenderer::<SnapshotRoot>::with_props(SnapshotRootProps {
+        collector: collector.clone(),
+        children: tree,
+    })
+    .hydratable(false)
+    .render()
+    .await;
+
+    collector
+        .tags()
+        .iter()
+        .filter_map(HeadEntry::from_html)
+        .collect()
+}
+
+/// Renders a component tree without a browser and returns the [`snapshot`] of its head
+/// elements.
+///
+/// # Example
+/// ```rust,no_run
+/// use next_rs::prelude::*;
+/// use next_rs::Head;
+/// use next_rs::testing::head_snapshot;
+///
+/// #[func]
+/// fn Page() -> Html {
+///     rsx! {
+///         <Head>
+///             <title>{"Pricing"}</title>
+///             <meta name="description" content="Plans for every team" />
+///         </Head>
+///     }
+/// }
+///
+/// async fn pricing_head() {
+///     assert_eq!(
+///         head_snapshot(rsx! { <Page /> }).await,
+///         "<meta charset=\"utf-8\">\n\
+///          <meta content=\"Plans for every team\" name=\"description\">\n\
+///          <title>Pricing</title>"
+///     );
+/// }
+/// ```
+#[cfg(feature = "ssr")]
+pub async fn head_snapshot(tree: Html) -> String {
+    snapshot(head_entries(tree).await)
+}